@@ -1,6 +1,10 @@
+pub mod clock;
+pub mod deque;
 pub mod graph;
 pub mod list;
+pub mod priority_queue;
 pub mod queue;
+pub mod random_generator;
 pub mod sset;
 pub mod stack;
 pub mod uset;
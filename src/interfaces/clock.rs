@@ -0,0 +1,48 @@
+use std::{cell::Cell, rc::Rc, time::Duration, time::Instant};
+
+/// Source of the current time for structures with expiring entries.
+/// Injecting it lets a test move time forward instead of sleeping.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so a
+/// test can keep one and hand the other to the structure under test.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+impl ManualClock {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self {
+            now: Rc::new(Cell::new(Instant::now())),
+        }
+    }
+    #[allow(unused)]
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration)
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
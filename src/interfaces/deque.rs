@@ -0,0 +1,11 @@
+use std::fmt::Debug;
+
+#[allow(unused)]
+pub trait Deque<T: Clone + Debug> {
+    fn push_front(&mut self, x: T) -> ();
+    fn push_back(&mut self, x: T) -> ();
+    fn pop_front(&mut self) -> Option<T>;
+    fn pop_back(&mut self) -> Option<T>;
+    fn front(&self) -> Option<T>;
+    fn back(&self) -> Option<T>;
+}
@@ -7,3 +7,58 @@ pub trait List<T: Clone + Default + Debug> {
     fn add(&mut self, i: usize, x: T) -> ();
     fn remove(&mut self, i: usize) -> Option<T>;
 }
+
+/// Checks `list` against a `Vec` through `steps` random `add`, `remove`
+/// and `set` calls, comparing every element after each one. `check` is
+/// called after each step for structure-specific invariants.
+#[cfg(test)]
+pub(crate) fn check_against_vec<T, L: List<T>>(
+    list: &mut L,
+    steps: usize,
+    mut check: impl FnMut(&L),
+) where
+    T: Clone + Default + Debug + PartialEq,
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    use rand::{thread_rng, Rng};
+    let mut rng = thread_rng();
+    let mut tobe = (0..list.size())
+        .filter_map(|i| list.get(i))
+        .collect::<Vec<_>>();
+    for _ in 0..steps {
+        match rng.gen_range(0..4) {
+            0 | 1 => {
+                let i = rng.gen_range(0..=tobe.len());
+                let x = rng.gen::<T>();
+                list.add(i, x.clone());
+                tobe.insert(i, x);
+            }
+            2 => {
+                if tobe.is_empty() {
+                    assert_eq!(list.remove(0), None);
+                    continue;
+                }
+                let i = rng.gen_range(0..tobe.len());
+                assert_eq!(list.remove(i), Some(tobe.remove(i)));
+            }
+            _ => {
+                if tobe.is_empty() {
+                    continue;
+                }
+                let i = rng.gen_range(0..tobe.len());
+                let x = rng.gen::<T>();
+                list.set(i, x.clone());
+                tobe[i] = x;
+            }
+        }
+        assert_eq!(list.size(), tobe.len());
+        assert_eq!(list.get(tobe.len()), None);
+        assert_eq!(
+            (0..list.size())
+                .filter_map(|i| list.get(i))
+                .collect::<Vec<_>>(),
+            tobe
+        );
+        check(list);
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt::Debug;
+
+/// A collection that hands out its elements in priority order, whichever
+/// element comes first under its `HeapOrder`.
+#[allow(unused)]
+pub trait PriorityQueue<T: Clone + Debug + PartialOrd> {
+    fn push(&mut self, x: T);
+    /// Removes the element that comes first.
+    fn pop(&mut self) -> Option<T>;
+    /// A clone of the element `pop` would return. It is owned because
+    /// `MeldableHeap` keeps its nodes in `Rc<RefCell<_>>` and cannot lend a
+    /// `&T` out of the borrow; `BinaryHeap::iter` gives a reference instead.
+    fn peek(&self) -> Option<T>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Moves every element of `other` into `self`.
+    fn merge(&mut self, other: Self)
+    where
+        Self: Sized;
+}
+
+/// Which of two elements a heap puts nearer the root.
+pub trait HeapOrder {
+    fn before<T: PartialOrd>(x: &T, y: &T) -> bool;
+}
+
+/// Smallest first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MinOrder;
+impl HeapOrder for MinOrder {
+    fn before<T: PartialOrd>(x: &T, y: &T) -> bool {
+        x < y
+    }
+}
+
+/// Largest first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaxOrder;
+impl HeapOrder for MaxOrder {
+    fn before<T: PartialOrd>(x: &T, y: &T) -> bool {
+        x > y
+    }
+}
@@ -0,0 +1,14 @@
+use rand::{rngs::StdRng, Rng};
+
+/// Source of randomness for the randomized structures (skip lists, `Treap`,
+/// `MeldableHeap`). Injecting it lets a test fix the shape of a structure,
+/// and seeding `StdRng` lets a failing run be replayed exactly.
+pub trait RandomGenerator {
+    fn gen_rand(&mut self) -> usize;
+}
+
+impl RandomGenerator for StdRng {
+    fn gen_rand(&mut self) -> usize {
+        self.gen()
+    }
+}
@@ -8,6 +8,10 @@ use rand::{thread_rng, Fill, Rng};
 use structs::{
     binary_tree::binary_easy::BinarySearchTree,
     external_memory::btree::BTree,
+    hash_tables::{
+        bench::bench,
+        hash_gen::{HashGen, SipHash, Tabulation},
+    },
     tries::{
         binary_trie::{BinaryTrie, ToUsize},
         x_fast_trie::XFastTrie,
@@ -18,6 +22,8 @@ mod interfaces;
 
 mod macros;
 
+mod sorting;
+
 mod structs;
 mod types;
 
@@ -27,6 +33,23 @@ impl ToUsize for i32 {
     }
 }
 fn main() {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("hash-bench") {
+        let n = args.next().and_then(|n| n.parse().ok()).unwrap_or(100_000);
+        println!("HashGen");
+        bench(HashGen::default(), n, 0)
+            .iter()
+            .for_each(|report| println!("{}", report));
+        println!("Tabulation");
+        bench(Tabulation::new(), n, 0)
+            .iter()
+            .for_each(|report| println!("{}", report));
+        println!("SipHash");
+        bench(SipHash::new(), n, 0)
+            .iter()
+            .for_each(|report| println!("{}", report));
+        return;
+    }
     let mut tree = BTree::new();
     for i in 0..10 {
         tree.add(i);
@@ -0,0 +1,6 @@
+pub mod counting_sort;
+pub mod heap_sort;
+pub mod helper;
+pub mod merge_sort;
+pub mod quick_sort;
+pub mod radix_sort;
@@ -0,0 +1,65 @@
+use crate::structs::tries::binary_trie::ToUsize;
+
+/// Sorts `a` by key, for keys in `0..k`, by counting how many elements have
+/// each key and placing each element after all smaller keys. Stable and
+/// O(n + k); it never compares two elements, so it returns 0.
+#[allow(unused)]
+pub fn counting_sort<T: ToUsize + Clone>(a: &mut [T], k: usize) -> usize {
+    sort_by_digit(a, k, |x| x.to_usize());
+    0
+}
+
+/// The counting sort pass shared with `radix_sort`: a stable sort of `a` by
+/// `digit`, whose values must be below `k`.
+pub(super) fn sort_by_digit<T: Clone>(a: &mut [T], k: usize, digit: impl Fn(&T) -> usize) {
+    let mut c = vec![0; k];
+    for x in a.iter() {
+        let d = digit(x);
+        if d >= k {
+            panic!("key {} is out of range 0..{}", d, k)
+        }
+        c[d] += 1;
+    }
+    // c[d] becomes the end of the run of elements with digit d
+    for i in 1..k {
+        c[i] += c[i - 1];
+    }
+    let b = a.to_vec();
+    for x in b.into_iter().rev() {
+        let d = digit(&x);
+        c[d] -= 1;
+        a[c[d]] = x;
+    }
+}
+
+#[cfg(test)]
+mod counting_sort_test {
+    use super::*;
+    #[test]
+    fn test() {
+        let mut a = vec![7, 2, 9, 0, 1, 2, 0, 9, 7, 4, 4, 6, 9, 1, 0, 9, 3, 2, 5, 9];
+        assert_eq!(counting_sort(&mut a, 10), 0);
+        assert_eq!(
+            a,
+            vec![0, 0, 0, 1, 1, 2, 2, 2, 3, 4, 4, 5, 6, 7, 7, 9, 9, 9, 9, 9]
+        );
+    }
+    #[test]
+    fn stable_test() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Item(usize, char);
+        impl ToUsize for Item {
+            fn to_usize(&self) -> usize {
+                self.0
+            }
+        }
+        let mut a = vec![Item(2, 'a'), Item(1, 'b'), Item(2, 'c'), Item(1, 'd')];
+        counting_sort(&mut a, 3);
+        assert_eq!(a.iter().map(|item| item.1).collect::<String>(), "bdac");
+    }
+    #[test]
+    #[should_panic]
+    fn out_of_range_test() {
+        counting_sort(&mut [1, 5], 5);
+    }
+}
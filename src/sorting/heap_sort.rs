@@ -0,0 +1,51 @@
+use std::{cell::Cell, fmt::Debug};
+
+use crate::structs::heap::binary_heap::BinaryHeap;
+
+use super::helper::Counted;
+
+/// Sorts `a` with a `BinaryHeap`: `from_vec` heapifies in O(n), then
+/// `into_sorted_vec` swaps the minimum to the end and trickles down the new
+/// root, `n - 1` times. At most `2 n log n` comparisons. Returns the number
+/// of comparisons.
+#[allow(unused)]
+pub fn heap_sort<T: Clone + Debug + PartialOrd>(a: &mut [T]) -> usize {
+    let comparisons = Cell::new(0);
+    let heap = BinaryHeap::from_vec(
+        a.iter()
+            .map(|x| Counted::new(x.clone(), &comparisons))
+            .collect(),
+    );
+    for (x, counted) in a.iter_mut().zip(heap.into_sorted_vec()) {
+        *x = counted.x;
+    }
+    comparisons.get()
+}
+
+#[cfg(test)]
+mod heap_sort_test {
+    use super::*;
+    use crate::sorting::helper::comparison_lower_bound;
+    use rand::{seq::SliceRandom, thread_rng};
+    #[test]
+    fn test() {
+        let mut a = vec![13, 8, 5, 2, 4, 0, 6, 9, 7, 3, 12, 1, 10, 11];
+        heap_sort(&mut a);
+        assert_eq!(a, (0..14).collect::<Vec<_>>());
+        let mut a = vec!["b", "c", "a", "b"];
+        heap_sort(&mut a);
+        assert_eq!(a, vec!["a", "b", "b", "c"]);
+        let mut a = vec![1];
+        assert_eq!(heap_sort(&mut a), 0);
+    }
+    #[test]
+    fn lower_bound_test() {
+        let n = 1000;
+        let mut a = (0..n).collect::<Vec<_>>();
+        a.shuffle(&mut thread_rng());
+        let comparisons = heap_sort(&mut a);
+        assert_eq!(a, (0..n).collect::<Vec<_>>());
+        assert!(comparisons as f64 >= comparison_lower_bound(n) - 20.0);
+        assert!(comparisons as f64 <= 2.0 * n as f64 * (n as f64).log2());
+    }
+}
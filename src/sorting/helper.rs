@@ -0,0 +1,47 @@
+use std::{cell::Cell, cmp::Ordering, fmt::Debug};
+
+/// `log2(n!)`, the fewest comparisons any comparison sort needs in the worst
+/// case, and on average over random permutations, to sort `n` elements.
+#[allow(unused)]
+pub fn comparison_lower_bound(n: usize) -> f64 {
+    (2..=n).map(|i| (i as f64).log2()).sum()
+}
+
+/// Sorts `a` with `slice::sort_by` and returns how many comparisons it made,
+/// as a baseline for the sorts in this module.
+#[allow(unused)]
+pub fn slice_sort<T: PartialOrd>(a: &mut [T]) -> usize {
+    let mut comparisons = 0;
+    a.sort_by(|x, y| {
+        comparisons += 1;
+        x.partial_cmp(y).unwrap_or(Ordering::Equal)
+    });
+    comparisons
+}
+
+/// Wraps an element so that every comparison of two wrapped elements is
+/// counted, for sorts that compare inside code they do not own.
+#[derive(Clone, Debug)]
+pub(super) struct Counted<'a, T: Clone + Debug + PartialOrd> {
+    pub(super) x: T,
+    comparisons: &'a Cell<usize>,
+}
+
+impl<'a, T: Clone + Debug + PartialOrd> Counted<'a, T> {
+    pub(super) fn new(x: T, comparisons: &'a Cell<usize>) -> Self {
+        Self { x, comparisons }
+    }
+}
+
+impl<'a, T: Clone + Debug + PartialOrd> PartialEq for Counted<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'a, T: Clone + Debug + PartialOrd> PartialOrd for Counted<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.comparisons.set(self.comparisons.get() + 1);
+        self.x.partial_cmp(&other.x)
+    }
+}
@@ -0,0 +1,86 @@
+/// Sorts `a` by splitting it in halves, sorting each and merging them.
+/// Stable, `n log n` comparisons at most. Returns the number of comparisons.
+#[allow(unused)]
+pub fn merge_sort<T: Clone + PartialOrd>(a: &mut [T]) -> usize {
+    let mut comparisons = 0;
+    sort(a, &mut comparisons);
+    comparisons
+}
+
+fn sort<T: Clone + PartialOrd>(a: &mut [T], comparisons: &mut usize) {
+    if a.len() <= 1 {
+        return;
+    }
+    let mut a0 = a[..a.len() / 2].to_vec();
+    let mut a1 = a[a.len() / 2..].to_vec();
+    sort(&mut a0, comparisons);
+    sort(&mut a1, comparisons);
+    merge(&a0, &a1, a, comparisons);
+}
+
+fn merge<T: Clone + PartialOrd>(a0: &[T], a1: &[T], a: &mut [T], comparisons: &mut usize) {
+    let (mut i0, mut i1) = (0, 0);
+    for x in a.iter_mut() {
+        let take_first = if i0 == a0.len() {
+            false
+        } else if i1 == a1.len() {
+            true
+        } else {
+            *comparisons += 1;
+            // `<=` keeps equal elements in their original order
+            a0[i0] <= a1[i1]
+        };
+        if take_first {
+            *x = a0[i0].clone();
+            i0 += 1;
+        } else {
+            *x = a1[i1].clone();
+            i1 += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod merge_sort_test {
+    use super::*;
+    use crate::sorting::helper::comparison_lower_bound;
+    use rand::{seq::SliceRandom, thread_rng};
+    #[test]
+    fn test() {
+        let mut a = vec![13, 8, 5, 2, 4, 0, 6, 9, 7, 3, 12, 1, 10, 11];
+        let comparisons = merge_sort(&mut a);
+        assert_eq!(a, (0..14).collect::<Vec<_>>());
+        assert!(comparisons <= 14 * 4);
+        let mut empty: Vec<i32> = vec![];
+        assert_eq!(merge_sort(&mut empty), 0);
+    }
+    #[test]
+    fn stable_test() {
+        /// Ordered by `key` only.
+        #[derive(Clone, Debug)]
+        struct Item(i32, char);
+        impl PartialEq for Item {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl PartialOrd for Item {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.0.partial_cmp(&other.0)
+            }
+        }
+        let mut a = vec![Item(2, 'a'), Item(1, 'b'), Item(2, 'c'), Item(1, 'd')];
+        merge_sort(&mut a);
+        assert_eq!(a.iter().map(|item| item.1).collect::<String>(), "bdac");
+    }
+    #[test]
+    fn lower_bound_test() {
+        let n = 1000;
+        let mut a = (0..n).collect::<Vec<_>>();
+        a.shuffle(&mut thread_rng());
+        let comparisons = merge_sort(&mut a);
+        assert_eq!(a, (0..n).collect::<Vec<_>>());
+        assert!(comparisons as f64 >= comparison_lower_bound(n) - 20.0);
+        assert!(comparisons as f64 <= n as f64 * (n as f64).log2());
+    }
+}
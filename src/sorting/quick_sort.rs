@@ -0,0 +1,101 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::interfaces::random_generator::RandomGenerator;
+
+/// Sorts `a` by partitioning it around a random pivot into the elements
+/// less than, equal to and greater than it, and sorting the outer parts.
+/// Each partition step spends one comparison on an element below the pivot
+/// and two on the rest, so distinct keys take about `1.5 * 1.39 n log n
+/// = 2.08 n log n` comparisons on average. Returns the number of
+/// comparisons.
+#[allow(unused)]
+pub fn quick_sort<T: PartialOrd>(a: &mut [T]) -> usize {
+    quick_sort_with_rng(a, &mut StdRng::from_entropy())
+}
+
+/// Like `quick_sort`, drawing pivots from `rand_gen` so a run can be
+/// replayed.
+#[allow(unused)]
+pub fn quick_sort_with_rng<T: PartialOrd, R: RandomGenerator>(
+    a: &mut [T],
+    rand_gen: &mut R,
+) -> usize {
+    let mut comparisons = 0;
+    sort(a, rand_gen, &mut comparisons);
+    comparisons
+}
+
+fn sort<T: PartialOrd, R: RandomGenerator>(a: &mut [T], rand_gen: &mut R, comparisons: &mut usize) {
+    if a.len() <= 1 {
+        return;
+    }
+    a.swap(0, rand_gen.gen_rand() % a.len());
+    // a[..p] < x, a[p..=j) == x, a[q..] > x with x = a[p] kept as the pivot
+    let (mut p, mut j, mut q) = (0, 1, a.len());
+    while j < q {
+        *comparisons += 1;
+        if a[j] < a[p] {
+            a.swap(j, p);
+            p += 1;
+            j += 1;
+        } else {
+            *comparisons += 1;
+            if a[j] > a[p] {
+                q -= 1;
+                a.swap(j, q);
+            } else {
+                j += 1;
+            }
+        }
+    }
+    let (less, rest) = a.split_at_mut(p);
+    sort(less, rand_gen, comparisons);
+    sort(&mut rest[q - p..], rand_gen, comparisons);
+}
+
+#[cfg(test)]
+mod quick_sort_test {
+    use super::*;
+    use crate::sorting::helper::{comparison_lower_bound, slice_sort};
+    use rand::{seq::SliceRandom, thread_rng};
+    #[test]
+    fn test() {
+        let mut a = vec![13, 8, 5, 2, 4, 0, 6, 9, 7, 3, 12, 1, 10, 11];
+        quick_sort(&mut a);
+        assert_eq!(a, (0..14).collect::<Vec<_>>());
+        let mut a = vec![3, 1, 3, 3, 2, 1, 3];
+        quick_sort(&mut a);
+        assert_eq!(a, vec![1, 1, 2, 3, 3, 3, 3]);
+        let mut a = vec![0.5, -1.0, 2.5];
+        quick_sort(&mut a);
+        assert_eq!(a, vec![-1.0, 0.5, 2.5]);
+    }
+    #[test]
+    fn seed_test() {
+        let mut a = (0..100).rev().collect::<Vec<_>>();
+        let mut b = a.clone();
+        let x = quick_sort_with_rng(&mut a, &mut StdRng::seed_from_u64(1));
+        let y = quick_sort_with_rng(&mut b, &mut StdRng::seed_from_u64(1));
+        assert_eq!(x, y);
+        assert_eq!(a, b);
+    }
+    #[test]
+    fn equal_keys_test() {
+        // the three-way partition finishes all-equal input in one pass
+        let mut a = vec![7; 1000];
+        assert_eq!(quick_sort(&mut a), 2 * 999);
+    }
+    #[test]
+    fn lower_bound_test() {
+        let n = 1000;
+        let mut a = (0..n).collect::<Vec<_>>();
+        a.shuffle(&mut thread_rng());
+        let mut b = a.clone();
+        let comparisons = quick_sort(&mut a);
+        assert_eq!(a, (0..n).collect::<Vec<_>>());
+        assert!(comparisons as f64 >= comparison_lower_bound(n) - 20.0);
+        // 1 comparison for smaller elements and 2 for the rest, around 2.08 n log n
+        assert!((comparisons as f64) < 3.0 * n as f64 * (n as f64).log2());
+        assert!(slice_sort(&mut b) as f64 >= comparison_lower_bound(n) - 20.0);
+    }
+}
@@ -0,0 +1,45 @@
+use crate::structs::tries::binary_trie::ToUsize;
+
+use super::counting_sort::sort_by_digit;
+
+/// Bits per digit: each pass counting-sorts on 8 bits of the key.
+const D: usize = 8;
+
+/// Sorts `a` by `to_usize` with one stable counting sort pass per 8-bit
+/// digit, least significant first, skipping the passes above the largest
+/// key. O(n) per pass and no comparisons, so it returns 0.
+#[allow(unused)]
+pub fn radix_sort<T: ToUsize + Clone>(a: &mut [T]) -> usize {
+    let max = a.iter().map(|x| x.to_usize()).max().unwrap_or(0);
+    let mut p = 0;
+    while p < usize::BITS as usize && max >> p > 0 {
+        sort_by_digit(a, 1 << D, |x| (x.to_usize() >> p) & ((1 << D) - 1));
+        p += D;
+    }
+    0
+}
+
+#[cfg(test)]
+mod radix_sort_test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+    #[test]
+    fn test() {
+        let mut a = vec![170, 45, 75, 90, 802, 24, 2, 66];
+        assert_eq!(radix_sort(&mut a), 0);
+        assert_eq!(a, vec![2, 24, 45, 66, 75, 90, 170, 802]);
+        let mut empty: Vec<i32> = vec![];
+        radix_sort(&mut empty);
+    }
+    #[test]
+    fn random_test() {
+        let mut rng = thread_rng();
+        let mut a = (0..10000)
+            .map(|_| rng.gen_range(0..i32::MAX))
+            .collect::<Vec<_>>();
+        let mut b = a.clone();
+        radix_sort(&mut a);
+        b.sort();
+        assert_eq!(a, b);
+    }
+}
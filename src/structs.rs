@@ -6,6 +6,8 @@ pub mod binary_tree {
     pub mod red_black_tree;
     pub mod scapegoat_tree;
 }
+pub mod bloom_filters;
+pub mod caches;
 pub mod external_memory {
     pub(super) mod block_store;
     pub mod btree;
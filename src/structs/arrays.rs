@@ -2,3 +2,7 @@ pub mod array_deque;
 pub mod array_queue;
 pub mod array_stack;
 pub mod dual_array_deque;
+mod helper;
+pub mod ring_buffer;
+pub mod rootish_array_stack;
+pub mod spsc_queue;
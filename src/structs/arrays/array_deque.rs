@@ -1,4 +1,9 @@
 use std::fmt::Debug;
+
+use crate::interfaces::{deque::Deque, list::List};
+
+use super::helper::{ring_slices, ring_slices_mut};
+pub use super::helper::{Iter, IterMut};
 #[derive(Debug)]
 pub struct ArrayDeque<T: Clone + Default + Debug> {
     array: Box<[Option<T>]>,
@@ -18,7 +23,7 @@ impl<T: Clone + Default + Debug> ArrayDeque<T> {
     }
     #[allow(unused)]
     pub fn get(&self, i: usize) -> Option<T> {
-        if i > self.n || i > self.array.len() {
+        if self.is_bound(i) {
             return None;
         }
         self.array
@@ -28,11 +33,11 @@ impl<T: Clone + Default + Debug> ArrayDeque<T> {
     }
     #[allow(unused)]
     pub fn set(&mut self, i: usize, x: T) {
-        let index = (self.j + i) % self.array.len();
-        let maybe = self.array.get(index);
-        if maybe.is_some() {
-            *self.array.get_mut(index).unwrap() = Some(x)
+        if self.is_bound(i) {
+            return;
         }
+        let index = (self.j + i) % self.array.len();
+        self.array[index] = Some(x)
     }
     #[allow(unused)]
     pub fn add(&mut self, i: usize, x: T) {
@@ -45,10 +50,10 @@ impl<T: Clone + Default + Debug> ArrayDeque<T> {
             } else {
                 self.j - 1
             };
-            for k in 0..(i - 1) {
+            for k in 0..i {
                 self.array.swap(
                     (self.j + k) % self.array.len(),
-                    (self.j - k) % self.array.len(),
+                    (self.j + k + 1) % self.array.len(),
                 )
             }
         } else {
@@ -72,7 +77,7 @@ impl<T: Clone + Default + Debug> ArrayDeque<T> {
             for k in (1..=i).rev() {
                 self.array.swap(
                     (self.j + k) % self.array.len(),
-                    (self.j - k) % self.array.len(),
+                    (self.j + k - 1) % self.array.len(),
                 );
             }
             self.j = (self.j + 1) % self.array.len();
@@ -88,10 +93,13 @@ impl<T: Clone + Default + Debug> ArrayDeque<T> {
         if 3 * self.n < self.array.len() {
             self.resize()
         }
-        self.j = 0;
         x
     }
     #[allow(unused)]
+    pub fn size(&self) -> usize {
+        self.n
+    }
+    #[allow(unused)]
     fn resize(&mut self) {
         let new_array = vec![Default::default(); (self.n * 2).max(1)];
         let mut old_array = std::mem::replace(&mut self.array, new_array.into_boxed_slice());
@@ -99,16 +107,139 @@ impl<T: Clone + Default + Debug> ArrayDeque<T> {
         for i in 0..self.n {
             self.array[i] = old_array[(i + self.j) % len].take();
         }
+        self.j = 0;
     }
     #[allow(unused)]
     fn is_bound(&self, i: usize) -> bool {
-        i > self.n || i > self.array.len()
+        i >= self.n
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.as_slices())
+    }
+    #[allow(unused)]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(self.as_mut_slices())
+    }
+    fn as_slices(&self) -> (&[Option<T>], &[Option<T>]) {
+        ring_slices(&self.array, self.j, self.n)
+    }
+    fn as_mut_slices(&mut self) -> (&mut [Option<T>], &mut [Option<T>]) {
+        ring_slices_mut(&mut self.array, self.j, self.n)
+    }
+}
+
+impl<T: Clone + Default + Debug> List<T> for ArrayDeque<T> {
+    fn size(&self) -> usize {
+        ArrayDeque::size(self)
+    }
+    fn get(&self, i: usize) -> Option<T> {
+        ArrayDeque::get(self, i)
+    }
+    fn set(&mut self, i: usize, x: T) {
+        ArrayDeque::set(self, i, x)
+    }
+    fn add(&mut self, i: usize, x: T) {
+        ArrayDeque::add(self, i, x)
+    }
+    fn remove(&mut self, i: usize) -> Option<T> {
+        ArrayDeque::remove(self, i)
+    }
+}
+
+impl<T: Clone + Default + Debug> Deque<T> for ArrayDeque<T> {
+    fn push_front(&mut self, x: T) {
+        self.add(0, x)
+    }
+    fn push_back(&mut self, x: T) {
+        self.add(self.n, x)
+    }
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+    fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.n.checked_sub(1)?)
+    }
+    fn front(&self) -> Option<T> {
+        self.get(0)
+    }
+    fn back(&self) -> Option<T> {
+        self.get(self.n.checked_sub(1)?)
+    }
+}
+
+pub struct IntoIter<T: Clone + Default + Debug>(std::vec::IntoIter<Option<T>>);
+impl<T: Clone + Default + Debug> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()?
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: Clone + Default + Debug> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()?
+    }
+}
+impl<T: Clone + Default + Debug> ExactSizeIterator for IntoIter<T> {}
+
+impl<T: Clone + Default + Debug> IntoIterator for ArrayDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        let mut array = self.array.into_vec();
+        array.rotate_left(self.j);
+        array.truncate(self.n);
+        IntoIter(array.into_iter())
+    }
+}
+impl<'a, T: Clone + Default + Debug> IntoIterator for &'a ArrayDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T: Clone + Default + Debug> IntoIterator for &'a mut ArrayDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+impl<T: Clone + Default + Debug> FromIterator<T> for ArrayDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = ArrayDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+impl<T: Clone + Default + Debug> Extend<T> for ArrayDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
     }
 }
 
 #[cfg(test)]
 mod array_deque_test {
     use super::*;
+    use crate::interfaces::list::check_against_vec;
+    #[test]
+    fn random_test() {
+        check_against_vec::<i32, _>(&mut ArrayDeque::new(), 1000, |deque| {
+            assert_eq!(
+                deque.iter().copied().collect::<Vec<_>>(),
+                (0..deque.size())
+                    .filter_map(|i| deque.get(i))
+                    .collect::<Vec<_>>()
+            );
+        });
+    }
     #[test]
     fn remove_test() {
         let mut deque = ArrayDeque::new();
@@ -149,4 +280,62 @@ mod array_deque_test {
         assert_eq!(deque.get(1).unwrap(), "you");
         assert_eq!(deque.get(10), None);
     }
+    #[test]
+    fn iter_test() {
+        let mut deque = ArrayDeque {
+            n: 3,
+            j: 2,
+            array: Box::new([Some("world"), None, Some("hello"), Some("good")]),
+        };
+        assert_eq!(
+            deque.iter().collect::<Vec<_>>(),
+            vec![&"hello", &"good", &"world"]
+        );
+        assert_eq!(
+            deque.iter().rev().collect::<Vec<_>>(),
+            vec![&"world", &"good", &"hello"]
+        );
+        let mut iter = deque.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&"world"));
+        assert_eq!(iter.next(), Some(&"hello"));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(&"good"));
+        assert_eq!(iter.next(), None);
+        for x in deque.iter_mut().rev().take(1) {
+            *x = "!!";
+        }
+        assert_eq!(deque.get(2), Some("!!"));
+        for x in &mut deque {
+            *x = "bye";
+        }
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some("bye"));
+        assert_eq!(iter.collect::<Vec<_>>(), vec!["bye"; 2]);
+    }
+    #[test]
+    fn deque_test() {
+        let mut deque = ArrayDeque::new();
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.back(), None);
+        deque.push_back("world");
+        deque.push_front("hello");
+        deque.push_back("!!");
+        assert_eq!(deque.front(), Some("hello"));
+        assert_eq!(deque.back(), Some("!!"));
+        assert_eq!(deque.pop_back(), Some("!!"));
+        assert_eq!(deque.pop_front(), Some("hello"));
+        assert_eq!(deque.pop_front(), Some("world"));
+        assert_eq!(deque.pop_front(), None);
+    }
+    #[test]
+    fn from_iter_test() {
+        let mut deque = (0..3).collect::<ArrayDeque<_>>();
+        deque.extend(3..5);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
 }
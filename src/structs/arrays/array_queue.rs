@@ -1,20 +1,36 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, iter::Chain, slice};
 
+use crate::interfaces::queue::Queue;
+
+use super::helper::{ring_slices, ring_slices_mut};
+
+/// A FIFO queue stored in a ring buffer that grows and shrinks with its
+/// contents. Free slots hold `T::default()`, so the queued elements can be
+/// lent out directly by `as_slices`.
+///
+/// `with_capacity` and `reserve` set a floor the buffer never shrinks below
+/// on `remove`, so a queue used as a staging area keeps its allocation;
+/// `shrink_to_fit` drops the floor again.
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct ArrayQueue<T: Clone + Debug + Default> {
+pub struct ArrayQueue<T: Clone + Debug + Default> {
     n: usize,
     j: usize,
-    array: Box<[Option<T>]>,
+    min_capacity: usize,
+    array: Box<[T]>,
 }
 
 impl<T: Clone + Debug + Default> ArrayQueue<T> {
     #[allow(unused)]
     pub fn new() -> Self {
-        let data = vec![];
+        Self::with_capacity(0)
+    }
+    #[allow(unused)]
+    pub fn with_capacity(capacity: usize) -> Self {
         ArrayQueue {
             n: 0,
             j: 0,
-            array: data.into_boxed_slice(),
+            min_capacity: capacity,
+            array: vec![T::default(); capacity].into_boxed_slice(),
         }
     }
     #[allow(unused)]
@@ -25,7 +41,7 @@ impl<T: Clone + Debug + Default> ArrayQueue<T> {
         *self
             .array
             .get_mut((self.j + self.n) % self.array.len())
-            .unwrap() = Some(x);
+            .unwrap() = x;
         self.n += 1;
     }
     #[allow(unused)]
@@ -33,21 +49,58 @@ impl<T: Clone + Debug + Default> ArrayQueue<T> {
         if self.is_bound(1) {
             return None;
         }
-        let x = self.array[self.j].take();
+        let x = std::mem::take(&mut self.array[self.j]);
         self.j = (self.j + 1) % self.array.len();
         self.n -= 1;
-        if self.array.len() >= 3 * self.n {
+        if self.array.len() >= 3 * self.n && self.array.len() > self.min_capacity {
             self.resize();
         }
-        x
+        Some(x)
+    }
+    #[allow(unused)]
+    pub fn peek(&self) -> Option<&T> {
+        if self.n == 0 {
+            return None;
+        }
+        Some(&self.array[self.j])
+    }
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.array.len()
+    }
+    /// Makes room for at least `additional` more elements without
+    /// reallocating, and keeps that room across later removals.
+    #[allow(unused)]
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = self.n + additional;
+        self.min_capacity = self.min_capacity.max(capacity);
+        if capacity > self.array.len() {
+            self.resize_to(capacity);
+        }
+    }
+    #[allow(unused)]
+    pub fn shrink_to_fit(&mut self) {
+        self.min_capacity = 0;
+        self.resize_to(self.n);
     }
     #[allow(unused)]
     pub fn resize(&mut self) {
-        let new_array = vec![Default::default(); (self.n * 2).max(1)];
+        self.resize_to((self.n * 2).max(1).max(self.min_capacity));
+    }
+    fn resize_to(&mut self, capacity: usize) {
+        let new_array = vec![Default::default(); capacity];
         let mut old_array = std::mem::replace(&mut self.array, new_array.into_boxed_slice());
         let len = old_array.len();
         for i in 0..self.n {
-            self.array[i] = old_array[(i + self.j) % len].take();
+            self.array[i] = std::mem::take(&mut old_array[(i + self.j) % len]);
         }
         self.j = 0;
     }
@@ -55,6 +108,85 @@ impl<T: Clone + Debug + Default> ArrayQueue<T> {
     fn is_bound(&self, i: usize) -> bool {
         i > self.n || i > self.array.len()
     }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (first, second) = self.as_slices();
+        first.iter().chain(second)
+    }
+    #[allow(unused)]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (first, second) = self.as_mut_slices();
+        first.iter_mut().chain(second)
+    }
+    /// The queued elements, oldest first, as the two contiguous halves of
+    /// the ring buffer. Neither half is copied.
+    #[allow(unused)]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        ring_slices(&self.array, self.j, self.n)
+    }
+    #[allow(unused)]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        ring_slices_mut(&mut self.array, self.j, self.n)
+    }
+}
+
+impl<T: Clone + Debug + Default + Eq + PartialEq> Queue<T> for ArrayQueue<T> {
+    fn add(&mut self, x: T) {
+        ArrayQueue::add(self, x)
+    }
+    fn remove(&mut self) -> Option<T> {
+        ArrayQueue::remove(self)
+    }
+}
+
+pub type Iter<'a, T> = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+pub type IterMut<'a, T> = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+pub struct IntoIter<T: Clone + Debug + Default>(ArrayQueue<T>);
+impl<T: Clone + Debug + Default> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.remove()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.n, Some(self.0.n))
+    }
+}
+
+impl<T: Clone + Debug + Default> IntoIterator for ArrayQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+impl<'a, T: Clone + Debug + Default> IntoIterator for &'a ArrayQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T: Clone + Debug + Default> IntoIterator for &'a mut ArrayQueue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+impl<T: Clone + Debug + Default> FromIterator<T> for ArrayQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = ArrayQueue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+impl<T: Clone + Debug + Default> Extend<T> for ArrayQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -81,7 +213,8 @@ mod array_queue_test {
             ArrayQueue {
                 n: 2,
                 j: 0,
-                array: Box::new([Some("hello"), Some("world")])
+                min_capacity: 0,
+                array: Box::new(["hello", "world"])
             }
         );
         array.add("goodbye");
@@ -92,18 +225,82 @@ mod array_queue_test {
             ArrayQueue {
                 n: 5,
                 j: 0,
-                array: Box::new([
-                    Some("hello"),
-                    Some("world"),
-                    Some("goodbye"),
-                    Some("world"),
-                    Some("thanks"),
-                    None,
-                    None,
-                    None
-                ])
+                min_capacity: 0,
+                array: Box::new(["hello", "world", "goodbye", "world", "thanks", "", "", ""])
             }
         );
         array.add("you");
     }
+    #[test]
+    fn iter_test() {
+        let mut array = ArrayQueue {
+            n: 3,
+            j: 2,
+            min_capacity: 0,
+            array: Box::new(["world", "", "hello", "good"]),
+        };
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![&"hello", &"good", &"world"]
+        );
+        for x in array.iter_mut() {
+            *x = "bye";
+        }
+        assert_eq!(array.into_iter().collect::<Vec<_>>(), vec!["bye"; 3]);
+        let mut array = (0..3).collect::<ArrayQueue<_>>();
+        array.extend(3..5);
+        assert_eq!((&array).into_iter().sum::<i32>(), 10);
+    }
+    #[test]
+    fn queue_test() {
+        fn drain<Q: Queue<i32>>(queue: &mut Q) -> Vec<i32> {
+            std::iter::from_fn(|| queue.remove()).collect()
+        }
+        let mut queue = ArrayQueue::new();
+        Queue::add(&mut queue, 1);
+        Queue::add(&mut queue, 2);
+        assert_eq!(queue.peek(), Some(&1));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(drain(&mut queue), vec![1, 2]);
+        assert_eq!(queue.peek(), None);
+        assert!(queue.is_empty());
+    }
+    #[test]
+    fn capacity_test() {
+        let mut queue = ArrayQueue::with_capacity(8);
+        assert_eq!(queue.capacity(), 8);
+        for i in 0..8 {
+            queue.add(i);
+        }
+        assert_eq!(queue.capacity(), 8);
+        for _ in 0..7 {
+            queue.remove();
+        }
+        assert_eq!(queue.capacity(), 8);
+        queue.reserve(20);
+        assert_eq!(queue.capacity(), 21);
+        assert_eq!(queue.peek(), Some(&7));
+        queue.shrink_to_fit();
+        assert_eq!(queue.capacity(), 1);
+        assert_eq!(queue.remove(), Some(7));
+        assert_eq!(queue.remove(), None);
+    }
+    #[test]
+    fn as_slices_test() {
+        let mut queue = ArrayQueue::with_capacity(4);
+        for i in 0..4 {
+            queue.add(i);
+        }
+        queue.remove();
+        queue.remove();
+        queue.add(4);
+        let (first, second) = queue.as_slices();
+        assert_eq!(first, &[2, 3]);
+        assert_eq!(second, &[4]);
+        assert_eq!(queue.as_slices().0.as_ptr(), queue.array[2..].as_ptr());
+        let (first, _) = queue.as_mut_slices();
+        first[0] = 5;
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&5, &3, &4]);
+        assert_eq!(queue.iter().rev().collect::<Vec<_>>(), vec![&4, &3, &5]);
+    }
 }
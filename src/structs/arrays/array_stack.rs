@@ -5,16 +5,6 @@ pub struct ArrayStack<T: Debug + Clone + Default> {
     array: Box<[Option<T>]>,
     n: usize,
 }
-impl<T: Debug + Clone + Default> Iterator for ArrayStack<T> {
-    type Item = T;
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(next) = self.remove(0) {
-            Some(next)
-        } else {
-            None
-        }
-    }
-}
 impl<T: Debug + Clone + Default> ArrayStack<T> {
     pub fn new() -> Self {
         ArrayStack {
@@ -48,7 +38,13 @@ impl<T: Debug + Clone + Default> ArrayStack<T> {
         }
     }
     pub fn get(&self, i: usize) -> Option<T> {
-        self.array.get(i).unwrap().clone()
+        self.array.get(i)?.clone()
+    }
+    pub fn get_ref(&self, i: usize) -> Option<&T> {
+        self.array.get(i)?.as_ref()
+    }
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        self.array.get_mut(i)?.as_mut()
     }
     pub fn add(&mut self, i: usize, x: T) {
         if (self.n + 1) > self.array.len() {
@@ -77,6 +73,14 @@ impl<T: Debug + Clone + Default> ArrayStack<T> {
         }
         x
     }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.array[..self.n].iter())
+    }
+    #[allow(unused)]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut(self.array[..self.n].iter_mut())
+    }
     fn resize(&mut self) {
         let new_array: Vec<Option<T>> = vec![Default::default(); (self.n * 2).max(1)];
         let mut new_array = new_array.into_boxed_slice();
@@ -87,6 +91,77 @@ impl<T: Debug + Clone + Default> ArrayStack<T> {
     }
 }
 
+pub struct Iter<'a, T: Debug + Clone + Default>(std::slice::Iter<'a, Option<T>>);
+impl<'a, T: Debug + Clone + Default> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()?.as_ref()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+pub struct IterMut<'a, T: Debug + Clone + Default>(std::slice::IterMut<'a, Option<T>>);
+impl<'a, T: Debug + Clone + Default> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()?.as_mut()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+pub struct IntoIter<T: Debug + Clone + Default>(std::vec::IntoIter<Option<T>>);
+impl<T: Debug + Clone + Default> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()?
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: Debug + Clone + Default> IntoIterator for ArrayStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        let mut array = self.array.into_vec();
+        array.truncate(self.n);
+        IntoIter(array.into_iter())
+    }
+}
+impl<'a, T: Debug + Clone + Default> IntoIterator for &'a ArrayStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T: Debug + Clone + Default> IntoIterator for &'a mut ArrayStack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+impl<T: Debug + Clone + Default> FromIterator<T> for ArrayStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array_stack = ArrayStack::new();
+        array_stack.extend(iter);
+        array_stack
+    }
+}
+impl<T: Debug + Clone + Default> Extend<T> for ArrayStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
+    }
+}
+
 #[cfg(test)]
 
 mod array_stack_test {
@@ -96,9 +171,25 @@ mod array_stack_test {
         let mut array_stack = ArrayStack::new();
         array_stack.add(0, "world");
         array_stack.add(0, "hello");
-        assert_eq!(array_stack.next(), Some("hello"));
-        assert_eq!(array_stack.next(), Some("world"));
-        assert_eq!(array_stack.next(), None);
+        let mut iter = array_stack.iter();
+        assert_eq!(iter.next(), Some(&"hello"));
+        assert_eq!(iter.next(), Some(&"world"));
+        assert_eq!(iter.next(), None);
+        assert_eq!(array_stack.size(), 2);
+        for x in array_stack.iter_mut() {
+            *x = "bye";
+        }
+        assert_eq!(
+            array_stack.into_iter().collect::<Vec<_>>(),
+            vec!["bye", "bye"]
+        );
+    }
+    #[test]
+    fn from_iter_test() {
+        let mut array_stack = (0..3).collect::<ArrayStack<_>>();
+        array_stack.extend(3..5);
+        assert_eq!(array_stack.size(), 5);
+        assert_eq!((&array_stack).into_iter().sum::<i32>(), 10);
     }
     #[test]
     fn remove_test() {
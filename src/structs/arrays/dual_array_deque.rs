@@ -1,14 +1,170 @@
 use std::fmt::Debug;
 
+use crate::interfaces::{deque::Deque, list::List};
+
 use super::array_stack::ArrayStack;
 
+/// A deque built from two `ArrayStack`s placed back to back.
+///
+/// `front` holds the first half of the list in reverse order and `back`
+/// holds the second half, so adding or removing near either end only
+/// shifts a few elements of one stack.
+#[derive(Debug)]
 pub struct DualArrayDeque<T: Clone + Default + Debug> {
     front: ArrayStack<T>,
     back: ArrayStack<T>,
 }
 
 impl<T: Clone + Default + Debug> DualArrayDeque<T> {
-    pub fn size(&self) -> usize {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self {
+            front: ArrayStack::new(),
+            back: ArrayStack::new(),
+        }
+    }
+    fn balance(&mut self) {
+        let front_size = self.front.size();
+        let back_size = self.back.size();
+        if 3 * front_size >= back_size && 3 * back_size >= front_size {
+            return;
+        }
+        let n = front_size + back_size;
+        let new_front_size = n / 2;
+        let front = (0..new_front_size)
+            .rev()
+            .filter_map(|i| self.get(i))
+            .collect::<ArrayStack<_>>();
+        let back = (new_front_size..n)
+            .filter_map(|i| self.get(i))
+            .collect::<ArrayStack<_>>();
+        self.front = front;
+        self.back = back;
+    }
+}
+
+impl<T: Clone + Default + Debug> List<T> for DualArrayDeque<T> {
+    fn size(&self) -> usize {
         self.front.size() + self.back.size()
     }
+    fn get(&self, i: usize) -> Option<T> {
+        if i < self.front.size() {
+            self.front.get(self.front.size() - i - 1)
+        } else {
+            self.back.get(i - self.front.size())
+        }
+    }
+    fn set(&mut self, i: usize, x: T) {
+        if i < self.front.size() {
+            self.front.set(self.front.size() - i - 1, x)
+        } else if i - self.front.size() < self.back.size() {
+            self.back.set(i - self.front.size(), x)
+        }
+    }
+    fn add(&mut self, i: usize, x: T) {
+        if i < self.front.size() {
+            self.front.add(self.front.size() - i, x)
+        } else {
+            self.back.add(i - self.front.size(), x)
+        }
+        self.balance()
+    }
+    fn remove(&mut self, i: usize) -> Option<T> {
+        let x = if i < self.front.size() {
+            self.front.remove(self.front.size() - i - 1)
+        } else {
+            self.back.remove(i - self.front.size())
+        };
+        self.balance();
+        x
+    }
+}
+
+impl<T: Clone + Default + Debug> Deque<T> for DualArrayDeque<T> {
+    fn push_front(&mut self, x: T) {
+        self.add(0, x)
+    }
+    fn push_back(&mut self, x: T) {
+        self.add(self.size(), x)
+    }
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+    fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.size().checked_sub(1)?)
+    }
+    fn front(&self) -> Option<T> {
+        self.get(0)
+    }
+    fn back(&self) -> Option<T> {
+        self.get(self.size().checked_sub(1)?)
+    }
+}
+
+#[cfg(test)]
+mod dual_array_deque_test {
+    use super::*;
+    use crate::interfaces::list::check_against_vec;
+    #[test]
+    fn add_test() {
+        let mut deque = DualArrayDeque::new();
+        deque.add(0, "world");
+        deque.add(0, "hello");
+        deque.add(2, "!!");
+        assert_eq!(deque.size(), 3);
+        assert_eq!(deque.get(0), Some("hello"));
+        assert_eq!(deque.get(1), Some("world"));
+        assert_eq!(deque.get(2), Some("!!"));
+        assert_eq!(deque.get(3), None);
+    }
+    #[test]
+    fn remove_test() {
+        let mut deque = DualArrayDeque::new();
+        deque.add(0, "hello");
+        deque.add(1, "world");
+        deque.add(2, "!!");
+        assert_eq!(deque.remove(1), Some("world"));
+        assert_eq!(deque.remove(0), Some("hello"));
+        assert_eq!(deque.remove(3), None);
+        assert_eq!(deque.remove(0), Some("!!"));
+        assert_eq!(deque.size(), 0);
+    }
+    #[test]
+    fn deque_test() {
+        let mut deque = DualArrayDeque::new();
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.front(), None);
+        for i in 0..10 {
+            deque.push_front(i);
+            deque.push_back(i);
+        }
+        assert_eq!(deque.front(), Some(9));
+        assert_eq!(deque.back(), Some(9));
+        for i in (0..10).rev() {
+            assert_eq!(deque.pop_front(), Some(i));
+        }
+        for i in (0..10).rev() {
+            assert_eq!(deque.pop_back(), Some(i));
+        }
+        assert_eq!(deque.size(), 0);
+    }
+    #[test]
+    fn balance_test() {
+        let mut deque = DualArrayDeque::new();
+        for i in 0..100 {
+            deque.add(i, i);
+        }
+        assert!(3 * deque.front.size() >= deque.back.size());
+        assert!(3 * deque.back.size() >= deque.front.size());
+        for _ in 0..90 {
+            deque.remove(0);
+        }
+        assert!(3 * deque.front.size() >= deque.back.size());
+        assert!(3 * deque.back.size() >= deque.front.size());
+        assert_eq!(deque.get(0), Some(90));
+    }
+    #[test]
+    fn random_test() {
+        check_against_vec::<i32, _>(&mut DualArrayDeque::new(), 1000, |_| {});
+    }
 }
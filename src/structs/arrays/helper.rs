@@ -0,0 +1,115 @@
+/// The `n` slots of a ring buffer starting at `j`, wrapping past the end of
+/// `array`, as its two contiguous halves.
+pub(super) fn ring_slices<S>(array: &[S], j: usize, n: usize) -> (&[S], &[S]) {
+    let len = array.len();
+    if j + n <= len {
+        (&array[j..(j + n)], &[])
+    } else {
+        let (second, first) = array.split_at(j);
+        (first, &second[..(j + n - len)])
+    }
+}
+
+pub(super) fn ring_slices_mut<S>(array: &mut [S], j: usize, n: usize) -> (&mut [S], &mut [S]) {
+    let len = array.len();
+    if j + n <= len {
+        (&mut array[j..(j + n)], &mut [])
+    } else {
+        let (second, first) = array.split_at_mut(j);
+        (first, &mut second[..(j + n - len)])
+    }
+}
+
+/// Iterates over the elements in the two halves from `ring_slices`, in
+/// order. Every slot must be `Some`.
+pub struct Iter<'a, T> {
+    first: std::slice::Iter<'a, Option<T>>,
+    second: std::slice::Iter<'a, Option<T>>,
+}
+impl<'a, T> Iter<'a, T> {
+    pub(super) fn new((first, second): (&'a [Option<T>], &'a [Option<T>])) -> Self {
+        Self {
+            first: first.iter(),
+            second: second.iter(),
+        }
+    }
+}
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())?.as_ref()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.second
+            .next_back()
+            .or_else(|| self.first.next_back())?
+            .as_ref()
+    }
+}
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    first: std::slice::IterMut<'a, Option<T>>,
+    second: std::slice::IterMut<'a, Option<T>>,
+}
+impl<'a, T> IterMut<'a, T> {
+    pub(super) fn new((first, second): (&'a mut [Option<T>], &'a mut [Option<T>])) -> Self {
+        Self {
+            first: first.iter_mut(),
+            second: second.iter_mut(),
+        }
+    }
+}
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.first.next() {
+            Some(x) => x.as_mut(),
+            None => self.second.next()?.as_mut(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.second.next_back() {
+            Some(x) => x.as_mut(),
+            None => self.first.next_back()?.as_mut(),
+        }
+    }
+}
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+#[cfg(test)]
+mod helper_test {
+    use super::*;
+    #[test]
+    fn ring_slices_test() {
+        let mut array = [Some(3), Some(4), None, Some(1), Some(2)];
+        assert_eq!(ring_slices(&array, 3, 4), (&array[3..], &array[..2]));
+        assert_eq!(ring_slices(&array, 0, 2), (&array[..2], &[][..]));
+        assert_eq!(ring_slices(&array, 1, 0), (&[][..], &[][..]));
+        let mut iter = IterMut::new(ring_slices_mut(&mut array, 3, 4));
+        assert_eq!(iter.len(), 4);
+        *iter.next_back().unwrap() = 5;
+        assert_eq!(
+            Iter::new(ring_slices(&array, 3, 4)).collect::<Vec<_>>(),
+            vec![&1, &2, &3, &5]
+        );
+        assert_eq!(
+            Iter::new(ring_slices(&array, 3, 4))
+                .rev()
+                .collect::<Vec<_>>(),
+            vec![&5, &3, &2, &1]
+        );
+    }
+}
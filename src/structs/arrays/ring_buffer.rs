@@ -0,0 +1,214 @@
+use std::fmt::Debug;
+
+use super::helper::{ring_slices, ring_slices_mut};
+pub use super::helper::{Iter, IterMut};
+
+/// What `RingBuffer::push` does when the buffer is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Drop the oldest element to make room for the new one.
+    Overwrite,
+    /// Keep the buffer as it is and hand the new element back.
+    Reject,
+}
+
+/// A fixed-capacity ring buffer laid out like `ArrayQueue`, except that it
+/// never reallocates: the backing array is sized once in `new`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RingBuffer<T: Clone + Debug> {
+    n: usize,
+    j: usize,
+    policy: OverflowPolicy,
+    array: Box<[Option<T>]>,
+}
+
+impl<T: Clone + Debug> RingBuffer<T> {
+    #[allow(unused)]
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        if capacity == 0 {
+            panic!("capacity must be positive")
+        }
+        RingBuffer {
+            n: 0,
+            j: 0,
+            policy,
+            array: vec![None; capacity].into_boxed_slice(),
+        }
+    }
+    /// Appends `x` as the newest element.
+    ///
+    /// Returns `Ok(None)` if there was room, `Ok(Some(oldest))` if the
+    /// oldest element was overwritten, and `Err(x)` if the buffer is full
+    /// and the policy is `Reject`.
+    #[allow(unused)]
+    pub fn push(&mut self, x: T) -> Result<Option<T>, T> {
+        let len = self.array.len();
+        if self.n < len {
+            self.array[(self.j + self.n) % len] = Some(x);
+            self.n += 1;
+            return Ok(None);
+        }
+        match self.policy {
+            OverflowPolicy::Reject => Err(x),
+            OverflowPolicy::Overwrite => {
+                let oldest = self.array[self.j].replace(x);
+                self.j = (self.j + 1) % len;
+                Ok(oldest)
+            }
+        }
+    }
+    /// Removes and returns the oldest element.
+    #[allow(unused)]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
+        let x = self.array[self.j].take();
+        self.j = (self.j + 1) % self.array.len();
+        self.n -= 1;
+        x
+    }
+    #[allow(unused)]
+    pub fn oldest(&self) -> Option<&T> {
+        if self.n == 0 {
+            return None;
+        }
+        self.array[self.j].as_ref()
+    }
+    #[allow(unused)]
+    pub fn newest(&self) -> Option<&T> {
+        if self.n == 0 {
+            return None;
+        }
+        self.array[(self.j + self.n - 1) % self.array.len()].as_ref()
+    }
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+    #[allow(unused)]
+    pub fn is_full(&self) -> bool {
+        self.n == self.array.len()
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.array.len()
+    }
+    #[allow(unused)]
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+    #[allow(unused)]
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+        self.j = 0;
+    }
+    /// Iterates from the oldest to the newest element.
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.as_slices())
+    }
+    #[allow(unused)]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(self.as_mut_slices())
+    }
+    fn as_slices(&self) -> (&[Option<T>], &[Option<T>]) {
+        ring_slices(&self.array, self.j, self.n)
+    }
+    fn as_mut_slices(&mut self) -> (&mut [Option<T>], &mut [Option<T>]) {
+        ring_slices_mut(&mut self.array, self.j, self.n)
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug>(RingBuffer<T>);
+impl<T: Clone + Debug> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.n, Some(self.0.n))
+    }
+}
+impl<T: Clone + Debug> ExactSizeIterator for IntoIter<T> {}
+
+impl<T: Clone + Debug> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+impl<'a, T: Clone + Debug> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T: Clone + Debug> IntoIterator for &'a mut RingBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod ring_buffer_test {
+    use super::*;
+    #[test]
+    fn overwrite_test() {
+        let mut buffer = RingBuffer::new(3, OverflowPolicy::Overwrite);
+        assert_eq!(buffer.push(1), Ok(None));
+        assert_eq!(buffer.push(2), Ok(None));
+        assert_eq!(buffer.push(3), Ok(None));
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push(4), Ok(Some(1)));
+        assert_eq!(buffer.push(5), Ok(Some(2)));
+        assert_eq!(buffer.oldest(), Some(&3));
+        assert_eq!(buffer.newest(), Some(&5));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.len(), 2);
+    }
+    #[test]
+    fn reject_test() {
+        let mut buffer = RingBuffer::new(2, OverflowPolicy::Reject);
+        assert_eq!(buffer.push("hello"), Ok(None));
+        assert_eq!(buffer.push("world"), Ok(None));
+        assert_eq!(buffer.push("!!"), Err("!!"));
+        assert_eq!(buffer.pop(), Some("hello"));
+        assert_eq!(buffer.push("!!"), Ok(None));
+        assert_eq!(buffer.into_iter().collect::<Vec<_>>(), vec!["world", "!!"]);
+    }
+    #[test]
+    fn iter_test() {
+        let mut buffer = RingBuffer::new(4, OverflowPolicy::Overwrite);
+        let array = buffer.array.as_ptr();
+        for i in 0..10 {
+            buffer.push(i).unwrap();
+        }
+        assert_eq!(buffer.array.as_ptr(), array);
+        assert_eq!(buffer.capacity(), 4);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![6, 7, 8, 9]);
+        assert_eq!(
+            buffer.iter().rev().copied().collect::<Vec<_>>(),
+            vec![9, 8, 7, 6]
+        );
+        assert_eq!(buffer.iter().len(), 4);
+        for x in &mut buffer {
+            *x *= 10;
+        }
+        assert_eq!((&buffer).into_iter().sum::<i32>(), 300);
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.oldest(), None);
+        assert_eq!(buffer.newest(), None);
+        assert_eq!(buffer.iter().next(), None);
+    }
+}
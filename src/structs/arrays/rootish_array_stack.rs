@@ -0,0 +1,160 @@
+use std::fmt::Debug;
+
+use crate::interfaces::list::List;
+
+use super::array_stack::ArrayStack;
+
+/// A list stored in blocks of sizes 1, 2, 3, ... so that at most
+/// O(sqrt(n)) slots are allocated but unused.
+#[derive(Debug)]
+pub struct RootishArrayStack<T: Clone + Default + Debug> {
+    blocks: ArrayStack<Box<[Option<T>]>>,
+    n: usize,
+}
+
+impl<T: Clone + Default + Debug> RootishArrayStack<T> {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self {
+            blocks: ArrayStack::new(),
+            n: 0,
+        }
+    }
+    /// Number of allocated slots that do not hold an element.
+    #[allow(unused)]
+    pub fn wasted_slots(&self) -> usize {
+        Self::capacity(self.blocks.size()) - self.n
+    }
+    fn capacity(r: usize) -> usize {
+        r * (r + 1) / 2
+    }
+    fn i2b(i: usize) -> usize {
+        let db = (-3.0 + (9.0 + 8.0 * i as f64).sqrt()) / 2.0;
+        db.ceil() as usize
+    }
+    fn slot(&mut self, i: usize) -> &mut Option<T> {
+        let b = Self::i2b(i);
+        let j = i - Self::capacity(b);
+        &mut self.blocks.get_mut(b).unwrap()[j]
+    }
+    fn grow(&mut self) {
+        let r = self.blocks.size();
+        let block = vec![None; r + 1].into_boxed_slice();
+        self.blocks.add(r, block);
+    }
+    fn shrink(&mut self) {
+        let mut r = self.blocks.size();
+        while r > 0 && r.saturating_sub(2) * (r - 1) / 2 >= self.n {
+            self.blocks.remove(r - 1);
+            r -= 1;
+        }
+    }
+}
+
+impl<T: Clone + Default + Debug> List<T> for RootishArrayStack<T> {
+    fn size(&self) -> usize {
+        self.n
+    }
+    fn get(&self, i: usize) -> Option<T> {
+        if i >= self.n {
+            return None;
+        }
+        let b = Self::i2b(i);
+        let j = i - Self::capacity(b);
+        self.blocks.get_ref(b)?[j].clone()
+    }
+    fn set(&mut self, i: usize, x: T) {
+        if i < self.n {
+            *self.slot(i) = Some(x);
+        }
+    }
+    fn add(&mut self, i: usize, x: T) {
+        if i > self.n {
+            panic!("do not adding index : {} ", i)
+        }
+        if Self::capacity(self.blocks.size()) < self.n + 1 {
+            self.grow();
+        }
+        self.n += 1;
+        for j in ((i + 1)..self.n).rev() {
+            let y = self.slot(j - 1).take();
+            *self.slot(j) = y;
+        }
+        *self.slot(i) = Some(x);
+    }
+    fn remove(&mut self, i: usize) -> Option<T> {
+        if i >= self.n {
+            return None;
+        }
+        let x = self.slot(i).take();
+        for j in i..(self.n - 1) {
+            let y = self.slot(j + 1).take();
+            *self.slot(j) = y;
+        }
+        self.n -= 1;
+        let r = self.blocks.size();
+        if r.saturating_sub(2) * (r - 1) / 2 >= self.n {
+            self.shrink();
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod rootish_array_stack_test {
+    use super::*;
+    use crate::interfaces::list::check_against_vec;
+    #[test]
+    fn i2b_test() {
+        let blocks = (0..10)
+            .map(RootishArrayStack::<i32>::i2b)
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, vec![0, 1, 1, 2, 2, 2, 3, 3, 3, 3]);
+    }
+    #[test]
+    fn add_test() {
+        let mut list = RootishArrayStack::new();
+        list.add(0, "world");
+        list.add(0, "hello");
+        list.add(2, "!!");
+        assert_eq!(list.size(), 3);
+        assert_eq!(list.get(0), Some("hello"));
+        assert_eq!(list.get(1), Some("world"));
+        assert_eq!(list.get(2), Some("!!"));
+        assert_eq!(list.get(3), None);
+        assert_eq!(list.blocks.size(), 2);
+        assert_eq!(list.wasted_slots(), 0);
+    }
+    #[test]
+    fn remove_test() {
+        let mut list = RootishArrayStack::new();
+        list.add(0, "hello");
+        list.add(1, "world");
+        list.add(2, "!!");
+        assert_eq!(list.remove(1), Some("world"));
+        assert_eq!(list.remove(3), None);
+        assert_eq!(list.remove(0), Some("hello"));
+        assert_eq!(list.remove(0), Some("!!"));
+        assert_eq!(list.size(), 0);
+        assert_eq!(list.blocks.size(), 0);
+    }
+    #[test]
+    fn wasted_slots_test() {
+        let mut list = RootishArrayStack::new();
+        for i in 0..2000 {
+            list.add(i, i);
+            let bound = 2.0 * (2.0 * list.size() as f64).sqrt() + 3.0;
+            assert!((list.wasted_slots() as f64) <= bound);
+        }
+        for _ in 0..1990 {
+            list.remove(0);
+            let bound = 2.0 * (2.0 * list.size() as f64).sqrt() + 3.0;
+            assert!((list.wasted_slots() as f64) <= bound);
+        }
+        assert_eq!(list.get(0), Some(1990));
+    }
+    #[test]
+    fn random_test() {
+        check_against_vec::<i32, _>(&mut RootishArrayStack::new(), 1000, |_| {});
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    cell::UnsafeCell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// A bounded single-producer single-consumer queue on the `ArrayQueue` ring
+/// layout, with the head and tail indices kept in atomics so the two ends
+/// can live on different threads.
+///
+/// The queue itself has no push or pop; `split` hands out the only
+/// `Producer` and the only `Consumer`, which is what makes the unsynchronized
+/// slot accesses below sound.
+#[derive(Debug)]
+pub struct SpscQueue<T> {
+    /// Position of the oldest element; only the consumer writes it.
+    ///
+    /// Both positions run modulo twice the capacity, so they never wrap
+    /// around `usize` and a full queue (`capacity` apart) differs from an
+    /// empty one (equal).
+    head: AtomicUsize,
+    /// Position of the next free slot; only the producer writes it.
+    tail: AtomicUsize,
+    array: Box<[UnsafeCell<Option<T>>]>,
+}
+
+// Slots are only touched by the producer between `head` and `tail` being
+// published, and by the consumer after, so sharing the queue is safe as long
+// as the elements themselves can move between threads.
+unsafe impl<T: Send> Sync for SpscQueue<T> {}
+
+impl<T> SpscQueue<T> {
+    #[allow(unused)]
+    pub fn new(capacity: usize) -> Self {
+        if capacity == 0 {
+            panic!("capacity must be positive")
+        }
+        SpscQueue {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            array: (0..capacity).map(|_| UnsafeCell::new(None)).collect(),
+        }
+    }
+    #[allow(unused)]
+    pub fn split(self) -> (Producer<T>, Consumer<T>) {
+        let queue = Arc::new(self);
+        (
+            Producer {
+                queue: queue.clone(),
+            },
+            Consumer { queue },
+        )
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.array.len()
+    }
+    fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        self.distance(head, tail)
+    }
+    /// How many positions `tail` is ahead of `head`.
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        let period = 2 * self.array.len();
+        (tail + period - head) % period
+    }
+    /// The position after `i`.
+    fn advance(&self, i: usize) -> usize {
+        (i + 1) % (2 * self.array.len())
+    }
+    fn slot(&self, i: usize) -> *mut Option<T> {
+        self.array[i % self.array.len()].get()
+    }
+}
+
+#[derive(Debug)]
+pub struct Producer<T> {
+    queue: Arc<SpscQueue<T>>,
+}
+
+impl<T> Producer<T> {
+    /// Appends `x`, or hands it back if the queue is full.
+    #[allow(unused)]
+    pub fn try_push(&mut self, x: T) -> Result<(), T> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        if self.queue.distance(head, tail) == self.queue.capacity() {
+            return Err(x);
+        }
+        // The consumer never reads this slot until `tail` is advanced below.
+        unsafe { *self.queue.slot(tail) = Some(x) };
+        self.queue
+            .tail
+            .store(self.queue.advance(tail), Ordering::Release);
+        Ok(())
+    }
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.queue.len() == 0
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+}
+
+#[derive(Debug)]
+pub struct Consumer<T> {
+    queue: Arc<SpscQueue<T>>,
+}
+
+impl<T> Consumer<T> {
+    /// Removes the oldest element, or returns `None` if the queue is empty.
+    #[allow(unused)]
+    pub fn try_pop(&mut self) -> Option<T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        // The producer never writes this slot until `head` is advanced below.
+        let x = unsafe { (*self.queue.slot(head)).take() };
+        self.queue
+            .head
+            .store(self.queue.advance(head), Ordering::Release);
+        x
+    }
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.queue.len() == 0
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+}
+
+impl<T> Iterator for Consumer<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_pop()
+    }
+}
+
+#[cfg(test)]
+mod spsc_queue_test {
+    use super::*;
+    use std::thread;
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+    #[test]
+    fn send_sync_test() {
+        assert_send::<Producer<String>>();
+        assert_send::<Consumer<String>>();
+        assert_sync::<SpscQueue<String>>();
+    }
+    #[test]
+    fn push_pop_test() {
+        let (mut producer, mut consumer) = SpscQueue::new(2).split();
+        assert_eq!(consumer.try_pop(), None);
+        assert_eq!(producer.try_push("hello"), Ok(()));
+        assert_eq!(producer.try_push("world"), Ok(()));
+        assert_eq!(producer.try_push("!!"), Err("!!"));
+        assert_eq!(producer.len(), 2);
+        assert_eq!(consumer.try_pop(), Some("hello"));
+        assert_eq!(producer.try_push("!!"), Ok(()));
+        assert_eq!(consumer.try_pop(), Some("world"));
+        assert_eq!(consumer.try_pop(), Some("!!"));
+        assert_eq!(consumer.try_pop(), None);
+        assert!(consumer.is_empty());
+    }
+    #[test]
+    fn wrap_test() {
+        let (mut producer, mut consumer) = SpscQueue::new(3).split();
+        for round in 0..10 {
+            for i in 0..3 {
+                assert_eq!(producer.try_push(round * 3 + i), Ok(()));
+            }
+            assert_eq!(producer.try_push(0), Err(0));
+            assert_eq!(consumer.len(), 3);
+            for i in 0..3 {
+                assert_eq!(consumer.try_pop(), Some(round * 3 + i));
+            }
+            assert!(consumer.is_empty());
+            // positions stay below twice the capacity
+            assert!(consumer.queue.head.load(Ordering::Relaxed) < 6);
+        }
+        assert_eq!(producer.try_push(1), Ok(()));
+        assert_eq!(producer.try_push(2), Ok(()));
+        assert_eq!(consumer.try_pop(), Some(1));
+        assert_eq!(producer.len(), 1);
+    }
+    #[test]
+    fn drop_test() {
+        let counter = Arc::new(());
+        let (mut producer, consumer) = SpscQueue::new(4).split();
+        for _ in 0..3 {
+            producer.try_push(counter.clone()).unwrap();
+        }
+        assert_eq!(Arc::strong_count(&counter), 4);
+        drop(producer);
+        drop(consumer);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+    #[test]
+    fn thread_test() {
+        const N: usize = 100_000;
+        let (mut producer, mut consumer) = SpscQueue::new(16).split();
+        let handle = thread::spawn(move || {
+            for i in 0..N {
+                let mut x = i.to_string();
+                while let Err(back) = producer.try_push(x) {
+                    x = back;
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut received = 0;
+        while received < N {
+            match consumer.try_pop() {
+                Some(x) => {
+                    assert_eq!(x, received.to_string());
+                    received += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        handle.join().unwrap();
+        assert_eq!(consumer.try_pop(), None);
+    }
+}
@@ -18,6 +18,83 @@ impl<T: Clone + PartialOrd + Ord + PartialEq + Debug> BinarySearchTree<T> {
     pub fn find(&self, x: T) -> bool {
         self.root.as_ref().map(|node| node.find(x)).unwrap_or(false)
     }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(self.root.as_ref());
+        iter
+    }
+}
+
+pub struct Iter<'a, T: Clone + PartialOrd + Ord + PartialEq + Debug> {
+    stack: Vec<&'a Node<T>>,
+}
+impl<'a, T: Clone + PartialOrd + Ord + PartialEq + Debug> Iter<'a, T> {
+    fn push_left_spine(&mut self, mut node: Option<&'a Node<T>>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+impl<'a, T: Clone + PartialOrd + Ord + PartialEq + Debug> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some(&node.data)
+    }
+}
+
+pub struct IntoIter<T: Clone + PartialOrd + Ord + PartialEq + Debug> {
+    stack: Vec<Node<T>>,
+}
+impl<T: Clone + PartialOrd + Ord + PartialEq + Debug> IntoIter<T> {
+    fn push_left_spine(&mut self, mut node: Option<Node<T>>) {
+        while let Some(mut current) = node {
+            node = current.left.take().map(|left| *left);
+            self.stack.push(current);
+        }
+    }
+}
+impl<T: Clone + PartialOrd + Ord + PartialEq + Debug> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut node = self.stack.pop()?;
+        self.push_left_spine(node.right.take().map(|right| *right));
+        Some(node.data)
+    }
+}
+
+impl<T: Clone + PartialOrd + Ord + PartialEq + Debug> IntoIterator for BinarySearchTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        let mut iter = IntoIter { stack: Vec::new() };
+        iter.push_left_spine(self.root);
+        iter
+    }
+}
+impl<'a, T: Clone + PartialOrd + Ord + PartialEq + Debug> IntoIterator for &'a BinarySearchTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + PartialOrd + Ord + PartialEq + Debug> FromIterator<T> for BinarySearchTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = BinarySearchTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+impl<T: Clone + PartialOrd + Ord + PartialEq + Debug> Extend<T> for BinarySearchTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
 }
 #[derive(Debug, PartialEq)]
 struct Node<T: Clone + PartialOrd + Ord + PartialEq + Debug> {
@@ -107,3 +184,17 @@ fn test() {
     let tobe = BinarySearchTree { root: Some(root) };
     assert_eq!(tree, tobe);
 }
+#[test]
+fn iter_test() {
+    let mut tree = vec![7, 3, 11].into_iter().collect::<BinarySearchTree<_>>();
+    tree.extend(vec![1, 5, 9, 13]);
+    assert_eq!(
+        tree.iter().collect::<Vec<_>>(),
+        vec![&1, &3, &5, &7, &9, &11, &13]
+    );
+    assert_eq!((&tree).into_iter().count(), 7);
+    assert_eq!(
+        tree.into_iter().collect::<Vec<_>>(),
+        vec![1, 3, 5, 7, 9, 11, 13]
+    );
+}
@@ -1,6 +1,7 @@
 use std::{
     cell::RefCell,
     fmt::Debug,
+    marker::PhantomData,
     ops::{Deref, DerefMut},
     rc::{Rc, Weak},
 };
@@ -395,6 +396,10 @@ impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> BinaryTree<T> {
             root: Some(WrapNode::new(root)),
         }
     }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.root.clone())
+    }
     pub fn remove(&mut self, value: T) -> Option<T> {
         if self.root.is_some() {
             if &self.root.as_ref().unwrap().borrow().value == &value {
@@ -472,6 +477,80 @@ impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> BinaryTree<T> {
     }
 }
 
+/// In-order traversal over shared nodes, yielding cloned values.
+pub struct IntoIter<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> {
+    stack: Vec<WrapNode<T>>,
+}
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> IntoIter<T> {
+    pub(super) fn new(root: Option<WrapNode<T>>) -> Self {
+        let mut iter = Self { stack: Vec::new() };
+        iter.push_left_spine(root);
+        iter
+    }
+    fn push_left_spine(&mut self, mut node: Option<WrapNode<T>>) {
+        while let Some(current) = node {
+            node = current.left();
+            self.stack.push(current);
+        }
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right());
+        Some(node.value())
+    }
+}
+
+pub struct Iter<'a, T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> {
+    inner: IntoIter<T>,
+    _tree: PhantomData<&'a T>,
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> Iter<'a, T> {
+    pub(super) fn new(root: Option<WrapNode<T>>) -> Self {
+        Self {
+            inner: IntoIter::new(root),
+            _tree: PhantomData,
+        }
+    }
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> IntoIterator for BinaryTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.root)
+    }
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> IntoIterator for &'a BinaryTree<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> FromIterator<T> for BinaryTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = BinaryTree { root: None };
+        tree.extend(iter);
+        tree
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> Extend<T> for BinaryTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod binary_tree_test {
 
@@ -642,4 +721,15 @@ mod binary_tree_test {
         assert_eq!(tree.remove(1), Some(1));
         assert_eq!(tree.remove(9), Some(9));
     }
+    #[test]
+    fn iter_test() {
+        let mut tree = vec![3, 1, 5].into_iter().collect::<BinaryTree<_>>();
+        tree.extend(vec![0, 4, 2, 6]);
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!((&tree).into_iter().count(), 7);
+        assert_eq!(
+            tree.into_iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5, 6]
+        );
+    }
 }
@@ -1,11 +1,14 @@
 use std::{
     cell::RefCell,
     fmt::Debug,
+    marker::PhantomData,
     ops::Deref,
     rc::{Rc, Weak},
 };
 
-use crate::interfaces::sset::SSet;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::interfaces::{random_generator::RandomGenerator, sset::SSet};
 #[derive(Debug, Clone)]
 struct TreapNode<T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord> {
     value: T,
@@ -131,30 +134,43 @@ impl<T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord> WrapNode<T>
     }
 }
 
-pub trait RandomGenerator {
-    fn gen_rand(&mut self) -> usize;
-}
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Treap<
     T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord,
-    R: RandomGenerator + Debug,
+    R: RandomGenerator + Debug = StdRng,
 > {
     root: WrapNode<T>,
     random_generator: R,
 }
 
+impl<T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord> Treap<T> {
+    /// Builds a treap whose priorities, and so whose shape, are fixed by
+    /// `seed`.
+    #[allow(unused)]
+    pub fn with_seed(root: T, seed: u64) -> Self {
+        Self::new(root, StdRng::seed_from_u64(seed))
+    }
+}
+
 impl<
         T: Default + Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord,
         R: RandomGenerator + Debug,
     > Treap<T, R>
 {
-    fn new(root: T, mut random_generator: R) -> Self {
+    pub fn new(root: T, mut random_generator: R) -> Self {
         let rand = random_generator.gen_rand();
         Self {
             root: WrapNode::new(root, rand),
             random_generator,
         }
     }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: IntoIter::new(self.root.clone()),
+            _tree: PhantomData,
+        }
+    }
     fn find_node(&self, value: T) -> Option<WrapNode<T>> {
         let mut node = Some(self.root.clone());
         while node.is_some() {
@@ -340,6 +356,78 @@ impl<
     }
 }
 
+pub struct IntoIter<T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord> {
+    stack: Vec<WrapNode<T>>,
+}
+impl<T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord> IntoIter<T> {
+    fn new(root: WrapNode<T>) -> Self {
+        let mut iter = Self { stack: Vec::new() };
+        iter.push_left_spine(Some(root));
+        iter
+    }
+    fn push_left_spine(&mut self, mut node: Option<WrapNode<T>>) {
+        while let Some(current) = node {
+            node = current.left();
+            self.stack.push(current);
+        }
+    }
+}
+impl<T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right());
+        let value = node.borrow().value.clone();
+        Some(value)
+    }
+}
+
+pub struct Iter<'a, T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord> {
+    inner: IntoIter<T>,
+    _tree: PhantomData<&'a T>,
+}
+impl<'a, T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<
+        T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord,
+        R: RandomGenerator + Debug,
+    > IntoIterator for Treap<T, R>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.root.clone())
+    }
+}
+impl<
+        'a,
+        T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord,
+        R: RandomGenerator + Debug,
+    > IntoIterator for &'a Treap<T, R>
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<
+        T: Clone + Default + Debug + Eq + PartialEq + PartialOrd + Ord,
+        R: RandomGenerator + Debug,
+    > Extend<T> for Treap<T, R>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod treap_tree_test {
     use std::rc::Rc;
@@ -358,6 +446,24 @@ mod treap_tree_test {
         assert_eq!(tree, make_test_tree())
     }
     #[test]
+    fn iter_test() {
+        let mut tree = make_test_tree();
+        tree.random_generator.0.extend(vec![7, 5]);
+        tree.extend(vec![0, 5]);
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!((&tree).into_iter().count(), 6);
+        assert_eq!(tree.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+    }
+    #[test]
+    fn seed_test() {
+        let keys = (0..100).map(|i| (i * 37) % 100).collect::<Vec<_>>();
+        let mut tree = Treap::with_seed(50, 7);
+        let mut other = Treap::with_seed(50, 7);
+        tree.extend(keys.clone());
+        other.extend(keys);
+        assert_eq!(tree, other);
+    }
+    #[test]
     fn size_test() {
         let tree = make_test_tree();
         assert_eq!(tree.size(), 4);
@@ -4,7 +4,7 @@ use std::{
     rc::Rc,
 };
 
-use super::binary_tree::{Tree, WrapNode};
+use super::binary_tree::{self, Tree, WrapNode};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 enum NodeColor {
@@ -52,6 +52,10 @@ where
     pub fn new() -> Self {
         Self { root: None }
     }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(binary_tree::Iter::new(self.root.clone()))
+    }
     pub fn add(&mut self, x: T) -> bool {
         let mut node = RedBlackNode::new(RedBlackNodeValue {
             value: x,
@@ -178,6 +182,57 @@ where
     }
 }
 
+pub struct Iter<'a, T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord>(
+    binary_tree::Iter<'a, RedBlackNodeValue<T>>,
+);
+impl<'a, T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|node| node.value)
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord>(
+    binary_tree::IntoIter<RedBlackNodeValue<T>>,
+);
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|node| node.value)
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> IntoIterator for RedBlackTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(binary_tree::IntoIter::new(self.root))
+    }
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> IntoIterator
+    for &'a RedBlackTree<T>
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> FromIterator<T> for RedBlackTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = RedBlackTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + PartialOrd + Ord> Extend<T> for RedBlackTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod red_black_tree_test {
     use crate::structs::binary_tree::{binary_tree::WrapNode, red_black_tree::*};
@@ -194,4 +249,12 @@ mod red_black_tree_test {
         //tree.add(7);
         println!("red_black_tree : {:#?}", tree);
     }
+    #[test]
+    fn iter_test() {
+        let mut tree = vec![2, 1].into_iter().collect::<RedBlackTree<_>>();
+        tree.extend(vec![5]);
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![1, 2, 5]);
+        assert_eq!((&tree).into_iter().count(), 3);
+        assert_eq!(tree.into_iter().collect::<Vec<_>>(), vec![1, 2, 5]);
+    }
 }
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use super::binary_tree::WrapNode;
+use super::binary_tree::{IntoIter, Iter, WrapNode};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ScapegoatTree<T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> {
@@ -9,6 +9,13 @@ pub struct ScapegoatTree<T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> {
 }
 
 impl<T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> ScapegoatTree<T> {
+    pub fn new() -> Self {
+        Self { root: None, q: 0 }
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.root.clone())
+    }
     pub fn remove(&mut self, x: T) -> Option<T> {
         if let Some(mut root) = self.root.clone() {
             if let Some(remove_node) = root.remove(x.clone()) {
@@ -26,22 +33,23 @@ impl<T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> ScapegoatTree<T> {
     pub fn add(&mut self, x: T) -> bool {
         let node = WrapNode::new(x.clone());
         if let Some(mut root) = self.root.clone() {
-            let result = root.add(x.clone());
+            if !root.add(x.clone()) {
+                return false;
+            }
+            self.q += 1;
             let depth = root.depth(x.clone()).unwrap();
             if depth > Self::log3_2(self.q) {
-                let mut parent = root.find_parent(x.clone()).unwrap();
-                let mut parent_size = parent.size();
-                let mut parent_parent_size = parent.parent().as_ref().unwrap().size();
-                while (3 * parent_size) <= (2 * parent_parent_size) {
-                    let new_parent = parent.parent().unwrap();
-                    parent = new_parent;
-                    parent_size = parent.size();
-                    parent_parent_size = parent.parent().as_ref().unwrap().size();
+                let mut child = root.find_last(x.clone()).unwrap();
+                while let Some(parent) = child.parent() {
+                    if 3 * child.size() > 2 * parent.size() {
+                        break;
+                    }
+                    child = parent;
                 }
-                self.rebuild(parent.parent().as_ref().unwrap().clone());
+                let scapegoat = child.parent().unwrap_or(child);
+                self.rebuild(scapegoat);
             }
-            self.q += 1;
-            result
+            true
         } else {
             self.root = Some(node);
             self.q = 1;
@@ -110,6 +118,37 @@ impl<T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> ScapegoatTree<T> {
     }
 }
 
+impl<T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> IntoIterator for ScapegoatTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.root)
+    }
+}
+impl<'a, T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> IntoIterator
+    for &'a ScapegoatTree<T>
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> FromIterator<T> for ScapegoatTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = ScapegoatTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+impl<T: Clone + Debug + PartialEq + Eq + PartialOrd + Ord> Extend<T> for ScapegoatTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod scapegoat_tree_test {
     use super::*;
@@ -135,6 +174,31 @@ mod scapegoat_tree_test {
         assert_eq!(tree, tobe);
     }
     #[test]
+    fn ascending_add_test() {
+        let mut tree = ScapegoatTree { root: None, q: 0 };
+        for x in 0..100 {
+            assert!(tree.add(x));
+        }
+        assert!(!tree.add(50));
+        assert_eq!(tree.q, 100);
+        let root = tree.root.clone().unwrap();
+        assert_eq!(root.size(), 100);
+        for x in 0..100 {
+            assert!(root.depth(x).unwrap() <= ScapegoatTree::<i32>::log3_2(tree.q));
+        }
+    }
+    #[test]
+    fn iter_test() {
+        let mut tree = vec![3, 1, 5].into_iter().collect::<ScapegoatTree<_>>();
+        tree.extend(vec![0, 4, 2, 6]);
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!((&tree).into_iter().count(), 7);
+        assert_eq!(
+            tree.into_iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5, 6]
+        );
+    }
+    #[test]
     fn add_test() {
         let mut tree = make_full_tree();
         tree.add(5);
@@ -0,0 +1,3 @@
+pub mod bloom_filter;
+pub mod counting_bloom_filter;
+mod helper;
@@ -0,0 +1,166 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use super::helper::Hashes;
+
+/// A set that answers `contains` with no false negatives and a tunable rate
+/// of false positives, using `m = 2^d` bits and `k` hashes per item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter<T: Hash> {
+    bits: Vec<u64>,
+    hashes: Hashes,
+    _item: PhantomData<T>,
+}
+
+impl<T: Hash> BloomFilter<T> {
+    /// Sizes the filter so that holding `expected_items` items gives about
+    /// `false_positive_rate` false positives.
+    #[allow(unused)]
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        Self::from_hashes(Hashes::for_rate(expected_items, false_positive_rate, None))
+    }
+    /// Like `new`, with the hash functions drawn from `seed`. Only filters
+    /// with the same shape and seed can be combined.
+    #[allow(unused)]
+    pub fn with_seed(expected_items: usize, false_positive_rate: f64, seed: u64) -> Self {
+        Self::from_hashes(Hashes::for_rate(
+            expected_items,
+            false_positive_rate,
+            Some(seed),
+        ))
+    }
+    fn from_hashes(hashes: Hashes) -> Self {
+        Self {
+            bits: vec![0; hashes.len().div_ceil(64)],
+            hashes,
+            _item: PhantomData,
+        }
+    }
+    #[allow(unused)]
+    pub fn add(&mut self, x: &T) {
+        for i in self.hashes.indexes(x) {
+            self.bits[i / 64] |= 1 << (i % 64);
+        }
+    }
+    /// `false` means `x` was never added; `true` means it probably was.
+    #[allow(unused)]
+    pub fn contains(&self, x: &T) -> bool {
+        self.hashes
+            .indexes(x)
+            .all(|i| self.bits[i / 64] & (1 << (i % 64)) != 0)
+    }
+    #[allow(unused)]
+    pub fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|word| *word = 0);
+    }
+    /// The number of bits `m`.
+    #[allow(unused)]
+    pub fn bit_len(&self) -> usize {
+        self.hashes.len()
+    }
+    /// The number of hashes `k`.
+    #[allow(unused)]
+    pub fn num_hashes(&self) -> usize {
+        self.hashes.k()
+    }
+    /// The false-positive rate at the current fill, `(ones / m)^k`.
+    #[allow(unused)]
+    pub fn false_positive_rate(&self) -> f64 {
+        let ones = self.bits.iter().map(|word| word.count_ones()).sum::<u32>();
+        (ones as f64 / self.bit_len() as f64).powi(self.num_hashes() as i32)
+    }
+    /// A filter holding every item of `self` or `other`.
+    #[allow(unused)]
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+    /// A filter that may contain only items of both `self` and `other`. It
+    /// can report more false positives than a filter built from the
+    /// intersection directly.
+    #[allow(unused)]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+    fn combine(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        if self.hashes != other.hashes {
+            panic!("filters must share size and hash functions")
+        }
+        Self {
+            bits: self
+                .bits
+                .iter()
+                .zip(other.bits.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            hashes: self.hashes.clone(),
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<T: Hash> Extend<T> for BloomFilter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(&x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod bloom_filter_test {
+    use super::*;
+    #[test]
+    fn test() {
+        let mut filter = BloomFilter::new(100, 0.01);
+        filter.add(&"hello");
+        filter.add(&"world");
+        assert!(filter.contains(&"hello"));
+        assert!(filter.contains(&"world"));
+        assert!(!filter.contains(&"good"));
+        filter.clear();
+        assert!(!filter.contains(&"hello"));
+    }
+    #[test]
+    fn shape_test() {
+        let filter = BloomFilter::<i32>::new(1000, 0.01);
+        // -1000 ln 0.01 / (ln 2)^2 is about 9585 bits
+        assert_eq!(filter.bit_len(), 16384);
+        assert_eq!(filter.num_hashes(), 11);
+        assert_eq!(filter.false_positive_rate(), 0.0);
+    }
+    #[test]
+    fn false_positive_rate_test() {
+        let n = 10000;
+        for rate in [0.1, 0.01, 0.001] {
+            let mut filter = BloomFilter::with_seed(n, rate, 1);
+            filter.extend(0..n);
+            assert!((0..n).all(|x| filter.contains(&x)));
+            let false_positives = (n..n * 11).filter(|x| filter.contains(x)).count();
+            let measured = false_positives as f64 / (n * 10) as f64;
+            assert!(measured < rate, "{} >= {}", measured, rate);
+            assert!(filter.false_positive_rate() < rate);
+        }
+    }
+    #[test]
+    fn union_intersection_test() {
+        let mut a = BloomFilter::with_seed(1000, 0.01, 7);
+        let mut b = BloomFilter::with_seed(1000, 0.01, 7);
+        a.extend(0..600);
+        b.extend(400..1000);
+        let union = a.union(&b);
+        assert!((0..1000).all(|x| union.contains(&x)));
+        let intersection = a.intersection(&b);
+        assert!((400..600).all(|x| intersection.contains(&x)));
+        let false_positives = (0..400)
+            .chain(600..1000)
+            .filter(|x| intersection.contains(x))
+            .count();
+        assert!(false_positives < 80);
+    }
+    #[test]
+    #[should_panic]
+    fn incompatible_test() {
+        let a = BloomFilter::<i32>::with_seed(1000, 0.01, 1);
+        let b = BloomFilter::<i32>::with_seed(1000, 0.01, 2);
+        a.union(&b);
+    }
+}
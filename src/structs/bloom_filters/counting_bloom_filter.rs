@@ -0,0 +1,177 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use super::helper::Hashes;
+
+/// A Bloom filter with a small counter in place of each bit, so items can be
+/// removed again. A counter that reaches `u8::MAX` stays there, since it no
+/// longer knows how many items share it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountingBloomFilter<T: Hash> {
+    counters: Vec<u8>,
+    hashes: Hashes,
+    _item: PhantomData<T>,
+}
+
+impl<T: Hash> CountingBloomFilter<T> {
+    #[allow(unused)]
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        Self::from_hashes(Hashes::for_rate(expected_items, false_positive_rate, None))
+    }
+    #[allow(unused)]
+    pub fn with_seed(expected_items: usize, false_positive_rate: f64, seed: u64) -> Self {
+        Self::from_hashes(Hashes::for_rate(
+            expected_items,
+            false_positive_rate,
+            Some(seed),
+        ))
+    }
+    fn from_hashes(hashes: Hashes) -> Self {
+        Self {
+            counters: vec![0; hashes.len()],
+            hashes,
+            _item: PhantomData,
+        }
+    }
+    #[allow(unused)]
+    pub fn add(&mut self, x: &T) {
+        for i in self.hashes.indexes(x) {
+            self.counters[i] = self.counters[i].saturating_add(1);
+        }
+    }
+    /// Removes one copy of `x`. Returns `false`, leaving the filter as it
+    /// was, if `x` is certainly not there. Removing an item that was never
+    /// added but shows up as a false positive can cause false negatives.
+    #[allow(unused)]
+    pub fn remove(&mut self, x: &T) -> bool {
+        if !self.contains(x) {
+            return false;
+        }
+        for i in self.hashes.indexes(x) {
+            if self.counters[i] < u8::MAX {
+                self.counters[i] -= 1;
+            }
+        }
+        true
+    }
+    #[allow(unused)]
+    pub fn contains(&self, x: &T) -> bool {
+        self.hashes.indexes(x).all(|i| self.counters[i] > 0)
+    }
+    /// An upper bound on how many times `x` was added.
+    #[allow(unused)]
+    pub fn count(&self, x: &T) -> usize {
+        self.hashes
+            .indexes(x)
+            .map(|i| self.counters[i] as usize)
+            .min()
+            .unwrap_or(0)
+    }
+    #[allow(unused)]
+    pub fn clear(&mut self) {
+        self.counters.iter_mut().for_each(|counter| *counter = 0);
+    }
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.counters.len()
+    }
+    #[allow(unused)]
+    pub fn num_hashes(&self) -> usize {
+        self.hashes.k()
+    }
+    /// A filter holding the items of both filters, with counts added.
+    #[allow(unused)]
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a.saturating_add(b))
+    }
+    /// A filter with, per counter, the smaller count of the two.
+    #[allow(unused)]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a.min(b))
+    }
+    fn combine(&self, other: &Self, f: impl Fn(u8, u8) -> u8) -> Self {
+        if self.hashes != other.hashes {
+            panic!("filters must share size and hash functions")
+        }
+        Self {
+            counters: self
+                .counters
+                .iter()
+                .zip(other.counters.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            hashes: self.hashes.clone(),
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<T: Hash> Extend<T> for CountingBloomFilter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(&x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod counting_bloom_filter_test {
+    use super::*;
+    #[test]
+    fn test() {
+        let mut filter = CountingBloomFilter::new(100, 0.01);
+        filter.add(&"hello");
+        filter.add(&"hello");
+        filter.add(&"world");
+        assert_eq!(filter.count(&"hello"), 2);
+        assert!(filter.remove(&"hello"));
+        assert!(filter.contains(&"hello"));
+        assert!(filter.remove(&"hello"));
+        assert!(!filter.contains(&"hello"));
+        assert!(!filter.remove(&"hello"));
+        assert!(filter.contains(&"world"));
+    }
+    #[test]
+    fn remove_test() {
+        let n = 10000;
+        let mut filter = CountingBloomFilter::with_seed(n, 0.01, 3);
+        filter.extend(0..n);
+        for x in (0..n).step_by(2) {
+            assert!(filter.remove(&x));
+        }
+        assert!((1..n).step_by(2).all(|x| filter.contains(&x)));
+        let false_positives = (0..n).step_by(2).filter(|x| filter.contains(x)).count();
+        assert!(false_positives < n / 2 / 100);
+        for x in (1..n).step_by(2) {
+            assert!(filter.remove(&x));
+        }
+        assert!(filter.counters.iter().all(|&counter| counter == 0));
+    }
+    #[test]
+    fn saturate_test() {
+        let mut filter = CountingBloomFilter::with_seed(10, 0.1, 1);
+        for _ in 0..300 {
+            filter.add(&1);
+        }
+        assert_eq!(filter.count(&1), 255);
+        for _ in 0..300 {
+            filter.remove(&1);
+        }
+        assert!(filter.contains(&1));
+    }
+    #[test]
+    fn union_intersection_test() {
+        let mut a = CountingBloomFilter::with_seed(1000, 0.01, 7);
+        let mut b = CountingBloomFilter::with_seed(1000, 0.01, 7);
+        a.extend(0..600);
+        b.extend(400..1000);
+        let mut union = a.union(&b);
+        assert!((0..1000).all(|x| union.contains(&x)));
+        assert!((400..600).all(|x| union.count(&x) >= 2));
+        for x in 0..1000 {
+            assert!(union.remove(&x));
+        }
+        assert!((400..600).all(|x| union.contains(&x)));
+        let intersection = a.intersection(&b);
+        assert!((400..600).all(|x| intersection.contains(&x)));
+    }
+}
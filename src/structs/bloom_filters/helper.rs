@@ -0,0 +1,58 @@
+use std::hash::{BuildHasher, Hash};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::structs::hash_tables::hash_gen::HashGen;
+
+/// `k` multiplicative hashes into a table of `2^d` slots, one `HashGen`
+/// with its own random odd `z` per hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Hashes {
+    hash_gens: Vec<HashGen>,
+    d: u32,
+}
+
+impl Hashes {
+    /// Picks `m = 2^d` and `k` for `n` items at false-positive rate `p`:
+    /// `m >= -n ln p / (ln 2)^2` and `k = (m / n) ln 2`.
+    pub(super) fn for_rate(n: usize, p: f64, seed: Option<u64>) -> Self {
+        if n == 0 {
+            panic!("expected number of items must be positive")
+        }
+        if !(p > 0.0 && p < 1.0) {
+            panic!("false positive rate must be in (0, 1) : {}", p)
+        }
+        let ln2 = std::f64::consts::LN_2;
+        let bits = -(n as f64) * p.ln() / (ln2 * ln2);
+        let mut d = 1;
+        while ((1_u64 << d) as f64) < bits {
+            d += 1;
+        }
+        let k = (((1_u64 << d) as f64 / n as f64) * ln2).round().max(1.0) as usize;
+        Self::new(d, k, seed)
+    }
+    pub(super) fn new(d: u32, k: usize, seed: Option<u64>) -> Self {
+        if d == 0 || d > 63 || k == 0 {
+            panic!("invalid filter shape d = {}, k = {}", d, k)
+        }
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let hash_gens = (0..k)
+            .map(|_| HashGen::new(rng.gen::<usize>() | 1, 64, d))
+            .collect();
+        Self { hash_gens, d }
+    }
+    pub(super) fn len(&self) -> usize {
+        1 << self.d
+    }
+    pub(super) fn k(&self) -> usize {
+        self.hash_gens.len()
+    }
+    pub(super) fn indexes<'a, T: Hash>(&'a self, x: &'a T) -> impl Iterator<Item = usize> + 'a {
+        self.hash_gens
+            .iter()
+            .map(move |hash_gen| (hash_gen.hash_one(x) >> (64 - self.d)) as usize)
+    }
+}
@@ -0,0 +1,3 @@
+mod arena;
+pub mod lfu_cache;
+pub mod lru_cache;
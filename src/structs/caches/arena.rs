@@ -0,0 +1,125 @@
+/// Doubly linked lists whose nodes live in one `Vec` and are addressed by
+/// index, so a node can be unlinked or moved in O(1) given its index. Several
+/// lists can share an arena; each is just its `ArenaList` ends.
+#[derive(Debug)]
+pub(super) struct Arena<T> {
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+}
+
+#[derive(Debug)]
+struct Node<T> {
+    x: T,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct ArenaList {
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+}
+
+impl ArenaList {
+    pub(super) fn back(&self) -> Option<usize> {
+        self.tail
+    }
+    pub(super) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> Arena<T> {
+    pub(super) fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+    pub(super) fn get(&self, i: usize) -> &T {
+        &self.node(i).x
+    }
+    pub(super) fn get_mut(&mut self, i: usize) -> &mut T {
+        &mut self.node_mut(i).x
+    }
+    /// Stores `x` at the front of `list` and returns its index.
+    pub(super) fn push_front(&mut self, list: &mut ArenaList, x: T) -> usize {
+        let node = Node {
+            x,
+            prev: None,
+            next: None,
+        };
+        let i = match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = Some(node);
+                i
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.link_front(list, i);
+        i
+    }
+    /// Takes node `i` out of `list` and frees its slot.
+    pub(super) fn remove(&mut self, list: &mut ArenaList, i: usize) -> T {
+        self.unlink(list, i);
+        self.free.push(i);
+        self.nodes[i].take().unwrap().x
+    }
+    pub(super) fn move_to_front(&mut self, list: &mut ArenaList, i: usize) {
+        self.unlink(list, i);
+        self.link_front(list, i);
+    }
+    /// Moves node `i` from the list `from` to the front of `to`.
+    pub(super) fn move_front(&mut self, from: &mut ArenaList, to: &mut ArenaList, i: usize) {
+        self.unlink(from, i);
+        self.link_front(to, i);
+    }
+    /// The elements of `list` from front to back.
+    pub(super) fn iter<'a>(&'a self, list: &ArenaList) -> impl Iterator<Item = &'a T> + 'a {
+        let mut i = list.head;
+        std::iter::from_fn(move || {
+            let node = self.node(i?);
+            i = node.next;
+            Some(&node.x)
+        })
+    }
+    fn link_front(&mut self, list: &mut ArenaList, i: usize) {
+        let head = list.head;
+        {
+            let node = self.node_mut(i);
+            node.prev = None;
+            node.next = head;
+        }
+        match head {
+            Some(head) => self.node_mut(head).prev = Some(i),
+            None => list.tail = Some(i),
+        }
+        list.head = Some(i);
+        list.len += 1;
+    }
+    fn unlink(&mut self, list: &mut ArenaList, i: usize) {
+        let (prev, next) = {
+            let node = self.node(i);
+            (node.prev, node.next)
+        };
+        match prev {
+            Some(prev) => self.node_mut(prev).next = next,
+            None => list.head = next,
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = prev,
+            None => list.tail = prev,
+        }
+        list.len -= 1;
+    }
+    fn node(&self, i: usize) -> &Node<T> {
+        self.nodes[i].as_ref().unwrap()
+    }
+    fn node_mut(&mut self, i: usize) -> &mut Node<T> {
+        self.nodes[i].as_mut().unwrap()
+    }
+}
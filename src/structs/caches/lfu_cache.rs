@@ -0,0 +1,421 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    interfaces::clock::{Clock, SystemClock},
+    structs::heap::indexed_binary_heap::{Handle, IndexedBinaryHeap},
+};
+
+use super::arena::{Arena, ArenaList};
+
+#[derive(Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    freq: usize,
+    expires_at: Option<Instant>,
+    deadline: Option<Handle>,
+}
+
+/// The entries used `freq` times, in recency order, and the nearest
+/// non-empty buckets below and above it.
+#[derive(Debug, Default)]
+struct Bucket {
+    list: ArenaList,
+    lower: Option<usize>,
+    higher: Option<usize>,
+}
+
+/// A cache holding at most `capacity` entries that evicts the least
+/// frequently used one, and the least recently used among those on a tie.
+///
+/// Entries with the same use count share a bucket, an arena list in
+/// recency order. The non-empty buckets are linked in frequency order and
+/// `min_freq` is the first of them, so `get`, `put`, `remove` and eviction
+/// are O(1). An expired entry is dropped when it is next looked up. Entries
+/// with an expiry also sit in `deadlines`, a min-heap on the expiry time,
+/// and a full cache drops every expired entry before evicting a live one;
+/// that costs O(log n) per entry with an expiry.
+#[derive(Debug)]
+pub struct LfuCache<K: Clone + Eq + Hash, V, C: Clock = SystemClock> {
+    capacity: usize,
+    map: HashMap<K, usize>,
+    arena: Arena<Entry<K, V>>,
+    buckets: HashMap<usize, Bucket>,
+    min_freq: usize,
+    deadlines: IndexedBinaryHeap<(Instant, usize)>,
+    ttl: Option<Duration>,
+    clock: C,
+}
+
+impl<K: Clone + Eq + Hash, V> LfuCache<K, V> {
+    #[allow(unused)]
+    pub fn new(capacity: usize) -> Self {
+        Self::with_clock_and_ttl(capacity, SystemClock, None)
+    }
+    #[allow(unused)]
+    pub fn with_ttl(capacity: usize, ttl: Duration) -> Self {
+        Self::with_clock_and_ttl(capacity, SystemClock, Some(ttl))
+    }
+}
+
+impl<K: Clone + Eq + Hash, V, C: Clock> LfuCache<K, V, C> {
+    #[allow(unused)]
+    pub fn with_clock_and_ttl(capacity: usize, clock: C, ttl: Option<Duration>) -> Self {
+        if capacity == 0 {
+            panic!("capacity must be positive")
+        }
+        Self {
+            capacity,
+            map: HashMap::new(),
+            arena: Arena::new(),
+            buckets: HashMap::new(),
+            min_freq: 0,
+            deadlines: IndexedBinaryHeap::new(),
+            ttl,
+            clock,
+        }
+    }
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Returns the value for `key` and counts a use of it.
+    #[allow(unused)]
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let i = self.live_index(key)?;
+        self.touch(i);
+        Some(&self.arena.get(i).value)
+    }
+    /// Returns the value for `key` without counting a use.
+    #[allow(unused)]
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let entry = self.arena.get(*self.map.get(key)?);
+        if self.is_expired(entry) {
+            return None;
+        }
+        Some(&entry.value)
+    }
+    #[allow(unused)]
+    pub fn contains(&self, key: &K) -> bool {
+        self.peek(key).is_some()
+    }
+    /// How many times `key` has been written or read since it was added.
+    #[allow(unused)]
+    pub fn frequency(&self, key: &K) -> Option<usize> {
+        self.peek(key)?;
+        Some(self.arena.get(self.map[key]).freq)
+    }
+    /// Inserts or replaces the value for `key`, evicting the least frequently
+    /// used entry if the cache is full. Replacing counts as a use.
+    #[allow(unused)]
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        let expires_at = self.ttl.map(|ttl| self.clock.now() + ttl);
+        self.insert(key, value, expires_at)
+    }
+    #[allow(unused)]
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) -> Option<V> {
+        let expires_at = Some(self.clock.now() + ttl);
+        self.insert(key, value, expires_at)
+    }
+    #[allow(unused)]
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self.map.remove(key)?;
+        let entry = self.unlink(i);
+        if self.is_expired(&entry) {
+            return None;
+        }
+        Some(entry.value)
+    }
+    fn insert(&mut self, key: K, value: V, expires_at: Option<Instant>) -> Option<V> {
+        if let Some(i) = self.live_index(&key) {
+            let old = std::mem::replace(&mut self.arena.get_mut(i).value, value);
+            self.set_expiry(i, expires_at);
+            self.touch(i);
+            return Some(old);
+        }
+        if self.map.len() == self.capacity {
+            self.drop_expired();
+        }
+        if self.map.len() == self.capacity {
+            self.evict();
+        }
+        if !self.buckets.contains_key(&1) {
+            self.link_bucket(1, None);
+        }
+        let bucket = self.buckets.get_mut(&1).unwrap();
+        let i = self.arena.push_front(
+            &mut bucket.list,
+            Entry {
+                key: key.clone(),
+                value,
+                freq: 1,
+                expires_at: None,
+                deadline: None,
+            },
+        );
+        self.set_expiry(i, expires_at);
+        self.map.insert(key, i);
+        None
+    }
+    /// Sets when node `i` expires, keeping `deadlines` in step.
+    fn set_expiry(&mut self, i: usize, expires_at: Option<Instant>) {
+        if let Some(h) = self.arena.get_mut(i).deadline.take() {
+            self.deadlines.remove(h);
+        }
+        let deadline = expires_at.map(|t| self.deadlines.add((t, i)));
+        let entry = self.arena.get_mut(i);
+        entry.expires_at = expires_at;
+        entry.deadline = deadline;
+    }
+    /// Drops every expired entry, soonest expiry first.
+    fn drop_expired(&mut self) {
+        while let Some((_, &(t, i))) = self.deadlines.peek() {
+            if t > self.clock.now() {
+                return;
+            }
+            let key = self.arena.get(i).key.clone();
+            self.map.remove(&key);
+            self.unlink(i);
+        }
+    }
+    /// Moves node `i` from its bucket to the next one up.
+    fn touch(&mut self, i: usize) {
+        let freq = self.arena.get(i).freq;
+        if !self.buckets.contains_key(&(freq + 1)) {
+            self.link_bucket(freq + 1, Some(freq));
+        }
+        let mut from = self.buckets.remove(&freq).unwrap();
+        let to = self.buckets.get_mut(&(freq + 1)).unwrap();
+        self.arena.move_front(&mut from.list, &mut to.list, i);
+        self.arena.get_mut(i).freq += 1;
+        let empty = from.list.is_empty();
+        self.buckets.insert(freq, from);
+        if empty {
+            self.unlink_bucket(freq);
+        }
+    }
+    fn evict(&mut self) {
+        let Some(i) = self
+            .buckets
+            .get(&self.min_freq)
+            .and_then(|bucket| bucket.list.back())
+        else {
+            return;
+        };
+        let key = self.arena.get(i).key.clone();
+        self.map.remove(&key);
+        self.unlink(i);
+    }
+    /// Takes node `i` out of its bucket and `deadlines` and frees it. The
+    /// caller removes it from `map`.
+    fn unlink(&mut self, i: usize) -> Entry<K, V> {
+        let freq = self.arena.get(i).freq;
+        let bucket = self.buckets.get_mut(&freq).unwrap();
+        let entry = self.arena.remove(&mut bucket.list, i);
+        if bucket.list.is_empty() {
+            self.unlink_bucket(freq);
+        }
+        if let Some(h) = entry.deadline {
+            self.deadlines.remove(h);
+        }
+        entry
+    }
+    /// Adds an empty bucket for `freq` just above the bucket `lower`, or
+    /// first if `lower` is `None`.
+    fn link_bucket(&mut self, freq: usize, lower: Option<usize>) {
+        let higher = match lower {
+            Some(lower) => self.buckets[&lower].higher,
+            None => self
+                .buckets
+                .contains_key(&self.min_freq)
+                .then_some(self.min_freq),
+        };
+        match lower {
+            Some(lower) => self.buckets.get_mut(&lower).unwrap().higher = Some(freq),
+            None => self.min_freq = freq,
+        }
+        if let Some(higher) = higher {
+            self.buckets.get_mut(&higher).unwrap().lower = Some(freq);
+        }
+        self.buckets.insert(
+            freq,
+            Bucket {
+                list: ArenaList::default(),
+                lower,
+                higher,
+            },
+        );
+    }
+    /// Drops the empty bucket for `freq`, joining its neighbours.
+    fn unlink_bucket(&mut self, freq: usize) {
+        let Bucket { lower, higher, .. } = self.buckets.remove(&freq).unwrap();
+        match lower {
+            Some(lower) => self.buckets.get_mut(&lower).unwrap().higher = higher,
+            None => self.min_freq = higher.unwrap_or(0),
+        }
+        if let Some(higher) = higher {
+            self.buckets.get_mut(&higher).unwrap().lower = lower;
+        }
+    }
+    fn live_index(&mut self, key: &K) -> Option<usize> {
+        let i = *self.map.get(key)?;
+        if self.is_expired(self.arena.get(i)) {
+            self.map.remove(key);
+            self.unlink(i);
+            return None;
+        }
+        Some(i)
+    }
+    fn is_expired(&self, entry: &Entry<K, V>) -> bool {
+        entry
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= self.clock.now())
+    }
+}
+
+#[cfg(test)]
+mod lfu_cache_test {
+    use super::*;
+    use crate::interfaces::clock::ManualClock;
+    use rand::{thread_rng, Rng};
+    #[test]
+    fn test() {
+        let mut cache = LfuCache::new(2);
+        assert_eq!(cache.put(1, "one"), None);
+        assert_eq!(cache.put(2, "two"), None);
+        assert_eq!(cache.get(&1), Some(&"one"));
+        assert_eq!(cache.get(&1), Some(&"one"));
+        assert_eq!(cache.frequency(&1), Some(3));
+        // 2 is used least
+        assert_eq!(cache.put(3, "three"), None);
+        assert!(!cache.contains(&2));
+        // 3 is used least
+        assert_eq!(cache.put(4, "four"), None);
+        assert!(!cache.contains(&3));
+        assert_eq!(cache.put(4, "cuatro"), Some("four"));
+        assert_eq!(cache.remove(&1), Some("one"));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.min_freq, 2);
+    }
+    #[test]
+    fn tie_test() {
+        let mut cache = LfuCache::new(3);
+        cache.put(1, 1);
+        cache.put(2, 2);
+        cache.put(3, 3);
+        cache.get(&1);
+        cache.get(&2);
+        cache.get(&3);
+        // all used twice, so the least recently used one goes
+        cache.put(4, 4);
+        assert!(!cache.contains(&1));
+        assert!(cache.contains(&2) && cache.contains(&3));
+        assert_eq!(cache.min_freq, 1);
+    }
+    #[test]
+    fn ttl_test() {
+        let clock = ManualClock::new();
+        let mut cache =
+            LfuCache::with_clock_and_ttl(2, clock.clone(), Some(Duration::from_secs(10)));
+        cache.put(1, 1);
+        cache.get(&1);
+        cache.get(&1);
+        cache.put_with_ttl(2, 2, Duration::from_secs(60));
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.min_freq, 1);
+        cache.put(3, 3);
+        assert!(cache.contains(&2) && cache.contains(&3));
+        // an expired entry makes room even when it is used more than the
+        // live ones
+        let clock = ManualClock::new();
+        let mut cache = LfuCache::with_clock_and_ttl(2, clock.clone(), None);
+        cache.put_with_ttl(1, 1, Duration::from_secs(10));
+        cache.get(&1);
+        cache.get(&1);
+        cache.put(2, 2);
+        clock.advance(Duration::from_secs(10));
+        cache.put(3, 3);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(&2) && cache.contains(&3));
+        assert!(cache.deadlines.is_empty());
+        cache.put_with_ttl(2, 2, Duration::from_secs(5));
+        cache.put(2, 2);
+        assert!(cache.deadlines.is_empty());
+    }
+    #[test]
+    fn random_test() {
+        let mut rng = thread_rng();
+        let capacity = 8;
+        let mut cache = LfuCache::new(capacity);
+        // (key, value, freq, last use)
+        let mut tobe: Vec<(i32, i32, usize, usize)> = Vec::new();
+        for time in 0..10000 {
+            let key = rng.gen_range(0..16);
+            let found = tobe.iter().position(|entry| entry.0 == key);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let value = rng.gen();
+                    match found {
+                        Some(i) => {
+                            let old = tobe[i].1;
+                            tobe[i] = (key, value, tobe[i].2 + 1, time);
+                            assert_eq!(cache.put(key, value), Some(old));
+                        }
+                        None => {
+                            if tobe.len() == capacity {
+                                let victim = (0..tobe.len())
+                                    .min_by_key(|&i| (tobe[i].2, tobe[i].3))
+                                    .unwrap();
+                                tobe.remove(victim);
+                            }
+                            tobe.push((key, value, 1, time));
+                            assert_eq!(cache.put(key, value), None);
+                        }
+                    }
+                }
+                1 => {
+                    let expected = found.map(|i| {
+                        tobe[i].2 += 1;
+                        tobe[i].3 = time;
+                        tobe[i].1
+                    });
+                    assert_eq!(cache.get(&key).copied(), expected);
+                }
+                _ => {
+                    let expected = found.map(|i| tobe.remove(i).1);
+                    assert_eq!(cache.remove(&key), expected);
+                }
+            }
+            assert_eq!(cache.len(), tobe.len());
+            for &(key, value, freq, _) in tobe.iter() {
+                assert_eq!(cache.peek(&key), Some(&value));
+                assert_eq!(cache.frequency(&key), Some(freq));
+            }
+            // the buckets are linked in increasing order from min_freq
+            let mut freqs = vec![];
+            let mut next = cache.buckets.get(&cache.min_freq).map(|_| cache.min_freq);
+            while let Some(freq) = next {
+                assert_eq!(cache.buckets[&freq].lower, freqs.last().copied());
+                freqs.push(freq);
+                next = cache.buckets[&freq].higher;
+            }
+            let mut keys = cache.buckets.keys().copied().collect::<Vec<_>>();
+            keys.sort();
+            assert_eq!(freqs, keys);
+        }
+    }
+}
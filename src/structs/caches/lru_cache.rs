@@ -0,0 +1,309 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use crate::interfaces::clock::{Clock, SystemClock};
+
+use super::arena::{Arena, ArenaList};
+
+#[derive(Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    expires_at: Option<Instant>,
+}
+
+/// A cache holding at most `capacity` entries that evicts the least recently
+/// used one. Entries are kept in recency order in an arena list, with a hash
+/// map from key to list node, so `get`, `put` and eviction are O(1).
+///
+/// Entries may carry an expiry time read from `clock`. Expired entries are
+/// never returned and are dropped lazily when they are looked up or reach
+/// the end of the list.
+#[derive(Debug)]
+pub struct LruCache<K: Clone + Eq + Hash, V, C: Clock = SystemClock> {
+    capacity: usize,
+    map: HashMap<K, usize>,
+    arena: Arena<Entry<K, V>>,
+    list: ArenaList,
+    ttl: Option<Duration>,
+    clock: C,
+}
+
+impl<K: Clone + Eq + Hash, V> LruCache<K, V> {
+    #[allow(unused)]
+    pub fn new(capacity: usize) -> Self {
+        Self::with_clock_and_ttl(capacity, SystemClock, None)
+    }
+    /// Entries added with `put` expire `ttl` after they were last written.
+    #[allow(unused)]
+    pub fn with_ttl(capacity: usize, ttl: Duration) -> Self {
+        Self::with_clock_and_ttl(capacity, SystemClock, Some(ttl))
+    }
+}
+
+impl<K: Clone + Eq + Hash, V, C: Clock> LruCache<K, V, C> {
+    #[allow(unused)]
+    pub fn with_clock_and_ttl(capacity: usize, clock: C, ttl: Option<Duration>) -> Self {
+        if capacity == 0 {
+            panic!("capacity must be positive")
+        }
+        Self {
+            capacity,
+            map: HashMap::new(),
+            arena: Arena::new(),
+            list: ArenaList::default(),
+            ttl,
+            clock,
+        }
+    }
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Returns the value for `key` and marks it most recently used.
+    #[allow(unused)]
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let i = self.live_index(key)?;
+        self.arena.move_to_front(&mut self.list, i);
+        Some(&self.arena.get(i).value)
+    }
+    /// Returns the value for `key` without touching its recency.
+    #[allow(unused)]
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let i = *self.map.get(key)?;
+        let entry = self.arena.get(i);
+        if self.is_expired(entry) {
+            return None;
+        }
+        Some(&entry.value)
+    }
+    #[allow(unused)]
+    pub fn contains(&self, key: &K) -> bool {
+        self.peek(key).is_some()
+    }
+    /// Inserts or replaces the value for `key`, evicting the least recently
+    /// used entry if the cache is full. Returns the replaced value.
+    #[allow(unused)]
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        let expires_at = self.ttl.map(|ttl| self.clock.now() + ttl);
+        self.insert(key, value, expires_at)
+    }
+    /// Like `put`, with an expiry of its own for this entry.
+    #[allow(unused)]
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) -> Option<V> {
+        let expires_at = Some(self.clock.now() + ttl);
+        self.insert(key, value, expires_at)
+    }
+    #[allow(unused)]
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self.map.remove(key)?;
+        let entry = self.arena.remove(&mut self.list, i);
+        if self.is_expired(&entry) {
+            return None;
+        }
+        Some(entry.value)
+    }
+    /// Removes the least recently used entry, dropping any expired entries
+    /// found behind it.
+    #[allow(unused)]
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        loop {
+            let i = self.list.back()?;
+            let entry = self.arena.remove(&mut self.list, i);
+            self.map.remove(&entry.key);
+            if !self.is_expired(&entry) {
+                return Some((entry.key, entry.value));
+            }
+        }
+    }
+    /// The keys from most to least recently used, skipping expired entries.
+    #[allow(unused)]
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.arena
+            .iter(&self.list)
+            .filter(|entry| !self.is_expired(entry))
+            .map(|entry| &entry.key)
+    }
+    /// Drops every expired entry, in O(len) time. Returns how many there were.
+    #[allow(unused)]
+    pub fn purge_expired(&mut self) -> usize {
+        let expired = self
+            .arena
+            .iter(&self.list)
+            .filter(|entry| self.is_expired(entry))
+            .map(|entry| entry.key.clone())
+            .collect::<Vec<_>>();
+        for key in expired.iter() {
+            let i = self.map.remove(key).unwrap();
+            self.arena.remove(&mut self.list, i);
+        }
+        expired.len()
+    }
+    fn insert(&mut self, key: K, value: V, expires_at: Option<Instant>) -> Option<V> {
+        if let Some(&i) = self.map.get(&key) {
+            let entry = self.arena.get_mut(i);
+            let was_expired = entry.expires_at.is_some_and(|at| at <= self.clock.now());
+            let old = std::mem::replace(&mut entry.value, value);
+            entry.expires_at = expires_at;
+            self.arena.move_to_front(&mut self.list, i);
+            return if was_expired { None } else { Some(old) };
+        }
+        if self.map.len() == self.capacity {
+            self.drop_expired_tail();
+        }
+        if self.map.len() == self.capacity {
+            self.pop_lru();
+        }
+        let i = self.arena.push_front(
+            &mut self.list,
+            Entry {
+                key: key.clone(),
+                value,
+                expires_at,
+            },
+        );
+        self.map.insert(key, i);
+        None
+    }
+    /// Drops expired entries from the least recently used end, stopping at
+    /// the first live one.
+    fn drop_expired_tail(&mut self) {
+        while let Some(i) = self.list.back() {
+            if !self.is_expired(self.arena.get(i)) {
+                return;
+            }
+            let entry = self.arena.remove(&mut self.list, i);
+            self.map.remove(&entry.key);
+        }
+    }
+    /// The node for `key`, dropping the entry if it has expired.
+    fn live_index(&mut self, key: &K) -> Option<usize> {
+        let i = *self.map.get(key)?;
+        if self.is_expired(self.arena.get(i)) {
+            self.map.remove(key);
+            self.arena.remove(&mut self.list, i);
+            return None;
+        }
+        Some(i)
+    }
+    fn is_expired(&self, entry: &Entry<K, V>) -> bool {
+        entry
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= self.clock.now())
+    }
+}
+
+#[cfg(test)]
+mod lru_cache_test {
+    use super::*;
+    use crate::interfaces::clock::ManualClock;
+    use rand::{thread_rng, Rng};
+    #[test]
+    fn test() {
+        let mut cache = LruCache::new(2);
+        assert_eq!(cache.put(1, "one"), None);
+        assert_eq!(cache.put(2, "two"), None);
+        assert_eq!(cache.get(&1), Some(&"one"));
+        assert_eq!(cache.put(3, "three"), None);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.keys().collect::<Vec<_>>(), vec![&3, &1]);
+        assert_eq!(cache.put(1, "uno"), Some("one"));
+        assert_eq!(cache.peek(&3), Some(&"three"));
+        assert_eq!(cache.pop_lru(), Some((3, "three")));
+        assert_eq!(cache.remove(&1), Some("uno"));
+        assert!(cache.is_empty());
+    }
+    #[test]
+    fn ttl_test() {
+        let clock = ManualClock::new();
+        let mut cache =
+            LruCache::with_clock_and_ttl(3, clock.clone(), Some(Duration::from_secs(10)));
+        cache.put(1, 1);
+        clock.advance(Duration::from_secs(5));
+        cache.put(2, 2);
+        cache.put_with_ttl(3, 3, Duration::from_secs(60));
+        assert_eq!(cache.get(&1), Some(&1));
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(cache.get(&1), None);
+        assert!(cache.contains(&2));
+        assert_eq!(cache.len(), 2);
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(cache.keys().collect::<Vec<_>>(), vec![&3]);
+        assert_eq!(cache.purge_expired(), 1);
+        assert_eq!(cache.len(), 1);
+        // a rewrite renews the expiry
+        cache.put(3, 30);
+        clock.advance(Duration::from_secs(9));
+        assert_eq!(cache.get(&3), Some(&30));
+    }
+    #[test]
+    fn expired_eviction_test() {
+        let clock = ManualClock::new();
+        let mut cache = LruCache::with_clock_and_ttl(2, clock.clone(), None);
+        cache.put_with_ttl(1, 1, Duration::from_secs(10));
+        cache.put(2, 2);
+        clock.advance(Duration::from_secs(10));
+        // the expired entry makes room, so 2 stays
+        cache.put(3, 3);
+        assert_eq!(cache.keys().collect::<Vec<_>>(), vec![&3, &2]);
+        cache.put(4, 4);
+        assert_eq!(cache.keys().collect::<Vec<_>>(), vec![&4, &3]);
+    }
+    #[test]
+    fn random_test() {
+        let mut rng = thread_rng();
+        let capacity = 8;
+        let mut cache = LruCache::new(capacity);
+        // most recently used first
+        let mut tobe: Vec<(i32, i32)> = Vec::new();
+        for _ in 0..10000 {
+            let key = rng.gen_range(0..16);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let value = rng.gen();
+                    let old = tobe
+                        .iter()
+                        .position(|&(k, _)| k == key)
+                        .map(|i| tobe.remove(i).1);
+                    if old.is_none() && tobe.len() == capacity {
+                        tobe.pop();
+                    }
+                    tobe.insert(0, (key, value));
+                    assert_eq!(cache.put(key, value), old);
+                }
+                1 => {
+                    let found = tobe
+                        .iter()
+                        .position(|&(k, _)| k == key)
+                        .map(|i| tobe.remove(i));
+                    if let Some(entry) = found {
+                        tobe.insert(0, entry);
+                    }
+                    assert_eq!(cache.get(&key).copied(), found.map(|(_, v)| v));
+                }
+                _ => {
+                    let found = tobe
+                        .iter()
+                        .position(|&(k, _)| k == key)
+                        .map(|i| tobe.remove(i));
+                    assert_eq!(cache.remove(&key), found.map(|(_, v)| v));
+                }
+            }
+            assert_eq!(
+                cache.keys().copied().collect::<Vec<_>>(),
+                tobe.iter().map(|&(k, _)| k).collect::<Vec<_>>()
+            );
+        }
+    }
+}
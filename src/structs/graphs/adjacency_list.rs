@@ -1,6 +1,6 @@
 use crate::{
-    interfaces::{graph::Graph, queue::Queue, stack::Stack},
-    structs::linked_lists::sl_list::SLList,
+    interfaces::{deque::Deque, graph::Graph},
+    structs::arrays::array_deque::ArrayDeque,
 };
 
 #[derive(Debug)]
@@ -23,22 +23,25 @@ impl AdjacencyList {
         }
     }
     pub fn dfs2(&self, i: usize) -> Vec<usize> {
+        self.dfs2_with(i, ArrayDeque::new())
+    }
+    /// Same as `dfs2`, using the given (empty) deque as the stack.
+    pub fn dfs2_with<D: Deque<usize>>(&self, i: usize, mut stack: D) -> Vec<usize> {
         let mut v = vec![];
-        self.private_dfs2(i, &mut v);
+        self.private_dfs2(i, &mut stack, &mut v);
         v
     }
-    fn private_dfs2(&self, i: usize, buffer: &mut Vec<usize>) {
-        let mut stack = SLList::new_with(vec![i]);
+    fn private_dfs2<D: Deque<usize>>(&self, i: usize, stack: &mut D, buffer: &mut Vec<usize>) {
+        stack.push_back(i);
         let mut colors = vec![Color::White; self.n];
-        while stack.size() > 0 {
-            let k = stack.pop().unwrap();
+        while let Some(k) = stack.pop_back() {
             let edge = self.out_edges(k);
             for j in edge {
                 let color = colors[j].clone();
                 if color == Color::White {
                     colors[j] = Color::Gray;
                     buffer.push(j);
-                    stack.push(j)
+                    stack.push_back(j)
                 }
             }
         }
@@ -63,20 +66,28 @@ impl AdjacencyList {
         colors[i] = Color::Black;
     }
     pub fn can_reach(&self, start: usize, target: usize) -> bool {
+        self.can_reach_with(start, target, ArrayDeque::new())
+    }
+    /// Same as `can_reach`, using the given (empty) deque as the BFS queue.
+    pub fn can_reach_with<D: Deque<usize>>(
+        &self,
+        start: usize,
+        target: usize,
+        mut queue: D,
+    ) -> bool {
         if start == target {
             return true;
         }
-        let mut queue = SLList::new_with(vec![start]);
+        queue.push_back(start);
         let mut seen = vec![false; self.n];
-        while queue.size() > 0 {
-            let i = queue.remove().unwrap();
+        while let Some(i) = queue.pop_front() {
             let i_adjacencies = self.out_edges(i);
             for adjacency in i_adjacencies {
                 if adjacency == target {
                     return true;
                 }
                 if !seen[adjacency] {
-                    queue.push(adjacency);
+                    queue.push_back(adjacency);
                     seen[adjacency] = true;
                 }
             }
@@ -122,6 +133,7 @@ impl Graph for AdjacencyList {
 #[cfg(test)]
 mod adjacency_list_test {
     use super::*;
+    use crate::structs::{arrays::dual_array_deque::DualArrayDeque, linked_lists::dl_list::DLList};
     #[test]
     fn dfs_test() {
         let mut al = AdjacencyList::new(6);
@@ -157,6 +169,19 @@ mod adjacency_list_test {
         assert!(!al.can_reach(4, 0));
     }
     #[test]
+    fn with_deque_test() {
+        let mut al = AdjacencyList::new(6);
+        al.add_edge(0, 1);
+        al.add_edge(0, 2);
+        al.add_edge(1, 3);
+        al.add_edge(1, 4);
+        al.add_edge(2, 5);
+        assert!(al.can_reach_with(0, 5, DLList::new()));
+        assert!(!al.can_reach_with(5, 1, DualArrayDeque::new()));
+        assert_eq!(al.dfs2_with(0, DLList::new()), vec![1, 2, 5, 3, 4]);
+        assert_eq!(al.dfs2_with(0, DualArrayDeque::new()), al.dfs2(0));
+    }
+    #[test]
     fn test() {
         let mut al = AdjacencyList::new(3);
         al.add_edge(0, 1);
@@ -1,3 +1,7 @@
+pub mod bench;
 pub mod chaned_hash_table;
-pub(self) mod hash_gen;
+pub mod cuckoo_hash_table;
+pub mod hash_gen;
+pub mod helper;
 pub mod liner_hash_table;
+pub mod robin_hood_hash_table;
@@ -0,0 +1,134 @@
+//! Runs `ChanedHashTable` and `LinerHashTable` over a few key patterns and
+//! reports their timing and `stats()`. `cargo run --release -- hash-bench [n]`
+//! prints the reports for every hasher in `hash_gen`.
+use std::{
+    fmt::{self, Display},
+    hash::BuildHasher,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::interfaces::uset::USet;
+
+use super::{
+    chaned_hash_table::ChanedHashTable, helper::HashTableStats, liner_hash_table::LinerHashTable,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyPattern {
+    Sequential,
+    Random,
+    /// Keys that differ only above bit 32, which any hash reading only the
+    /// low half of a word sends to a single slot.
+    Adversarial,
+}
+
+impl KeyPattern {
+    pub const ALL: [KeyPattern; 3] = [
+        KeyPattern::Sequential,
+        KeyPattern::Random,
+        KeyPattern::Adversarial,
+    ];
+    pub fn keys(self, n: usize, seed: u64) -> Vec<u64> {
+        match self {
+            KeyPattern::Sequential => (0..n as u64).collect(),
+            KeyPattern::Random => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                (0..n).map(|_| rng.gen()).collect()
+            }
+            KeyPattern::Adversarial => (0..n as u64).map(|i| i << 32).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BenchReport {
+    pub table: &'static str,
+    pub pattern: KeyPattern,
+    /// Time to add every key, find every key and remove every other one.
+    pub elapsed: Duration,
+    /// The longest chain, or the longest probe sequence.
+    pub longest: usize,
+    /// Taken after the workload, so removals show up as tombstones.
+    pub stats: HashTableStats,
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<16} {:<12} {:>12} load {:.3} longest {:>6} tombstones {:>6} resizes {:>3}",
+            self.table,
+            format!("{:?}", self.pattern),
+            format!("{:.2?}", self.elapsed),
+            self.stats.load_factor,
+            self.longest,
+            self.stats.tombstones,
+            self.stats.resizes
+        )
+    }
+}
+
+#[allow(unused)]
+pub fn bench<S: BuildHasher + Clone>(hasher: S, n: usize, seed: u64) -> Vec<BenchReport> {
+    let mut reports = Vec::new();
+    for pattern in KeyPattern::ALL {
+        let keys = pattern.keys(n, seed);
+        let mut chaned = ChanedHashTable::with_hasher(hasher.clone());
+        let elapsed = workload(&mut chaned, &keys);
+        let stats = chaned.stats();
+        reports.push(BenchReport {
+            table: "ChanedHashTable",
+            pattern,
+            elapsed,
+            longest: stats.histogram.len() - 1,
+            stats,
+        });
+        let mut liner = LinerHashTable::with_hasher(hasher.clone());
+        let elapsed = workload(&mut liner, &keys);
+        let stats = liner.stats();
+        reports.push(BenchReport {
+            table: "LinerHashTable",
+            pattern,
+            elapsed,
+            longest: stats.histogram.len(),
+            stats,
+        });
+    }
+    reports
+}
+
+fn workload<U: USet<u64>>(table: &mut U, keys: &[u64]) -> Duration {
+    let start = Instant::now();
+    for &x in keys {
+        table.add(x);
+    }
+    for &x in keys {
+        table.find(x);
+    }
+    for &x in keys.iter().step_by(2) {
+        table.remove(x);
+    }
+    start.elapsed()
+}
+
+#[cfg(test)]
+mod bench_test {
+    use super::super::hash_gen::{HashGen, Tabulation};
+    use super::*;
+    #[test]
+    fn bench_test() {
+        let n = 500;
+        let reports = bench(HashGen::default(), n, 1);
+        assert_eq!(reports.len(), 6);
+        for report in reports.iter() {
+            assert_eq!(report.stats.size, n / 2);
+            assert!(report.longest < 64);
+        }
+        for report in bench(Tabulation::with_seed(1), n, 1) {
+            assert!(report.longest < 64);
+            assert!(report.to_string().starts_with(report.table));
+        }
+    }
+}
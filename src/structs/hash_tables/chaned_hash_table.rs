@@ -1,41 +1,104 @@
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+};
 
-use crate::{interfaces::uset::USet, structs::arrays::array_stack::ArrayStack};
+use crate::{
+    interfaces::uset::USet,
+    structs::arrays::array_stack::{self, ArrayStack},
+};
 
-use super::hash_gen::HashGen;
+use super::{hash_gen::HashGen, helper::HashTableStats};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ChanedHashTable<T: Clone + Eq + PartialEq + Debug + Default + ToString> {
+pub struct ChanedHashTable<
+    T: Clone + Eq + PartialEq + Debug + Default + Hash,
+    S: BuildHasher = HashGen,
+> {
     array: Box<[Option<ArrayStack<T>>]>,
     n: usize,
+    d: usize,
+    resizes: usize,
+    hasher: S,
 }
 
-impl<T: Clone + Eq + PartialEq + Debug + Default + ToString> ChanedHashTable<T> {
+impl<T: Clone + Eq + PartialEq + Debug + Default + Hash> ChanedHashTable<T> {
     #[allow(unused)]
     pub fn new() -> Self {
-        let v = vec![None; 2_i32.pow(Self::d() as u32) as usize];
+        Self::with_hasher(HashGen::default())
+    }
+}
+
+impl<T: Clone + Eq + PartialEq + Debug + Default + Hash, S: BuildHasher> ChanedHashTable<T, S> {
+    #[allow(unused)]
+    pub fn with_hasher(hasher: S) -> Self {
+        let d = 1;
+        let v = vec![None; 1 << d];
         Self {
             array: v.into_boxed_slice(),
             n: 0,
+            d,
+            resizes: 0,
+            hasher,
+        }
+    }
+    #[allow(unused)]
+    pub fn hasher(&self) -> &S {
+        &self.hasher
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            buckets: self.array.iter(),
+            bucket: None,
         }
     }
-    fn w() -> usize {
-        32
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.array.len()
     }
-    fn d() -> usize {
-        8
+    /// The average chain length `n / 2^d`. Resizing keeps it between 1/3
+    /// and 1.
+    #[allow(unused)]
+    pub fn load_factor(&self) -> f64 {
+        self.n as f64 / self.capacity() as f64
+    }
+    /// A snapshot of the size, load factor and chain-length histogram.
+    #[allow(unused)]
+    pub fn stats(&self) -> HashTableStats {
+        let mut histogram = vec![0; self.chain_lens().max().unwrap_or(0) + 1];
+        for len in self.chain_lens() {
+            histogram[len] += 1;
+        }
+        HashTableStats {
+            size: self.n,
+            capacity: self.capacity(),
+            load_factor: self.load_factor(),
+            histogram,
+            tombstones: 0,
+            resizes: self.resizes,
+        }
     }
-    fn z() -> usize {
-        4102541685
+    fn chain_lens(&self) -> impl Iterator<Item = usize> + '_ {
+        self.array
+            .iter()
+            .map(|bucket| bucket.as_ref().map_or(0, |bucket| bucket.size()))
     }
+    /// Rebuilds the table with the smallest `2^d > n` buckets and rehashes
+    /// every chain into it.
     fn resize(&mut self) {
-        let expand_array_len = (self.n * 2).max(1);
-        let v = vec![None; expand_array_len];
-        let mut new_array = v.into_boxed_slice();
-        for i in 0..self.n {
-            new_array[i] = self.array[i].take();
+        self.resizes += 1;
+        self.d = 1;
+        while (1 << self.d) <= self.n {
+            self.d += 1;
+        }
+        let v = vec![None; 1 << self.d];
+        let old_array = std::mem::replace(&mut self.array, v.into_boxed_slice());
+        for bucket in old_array.into_vec().into_iter().flatten() {
+            for x in bucket {
+                self.set(self.hash(&x), x);
+            }
         }
-        self.array = new_array
     }
     fn set(&mut self, i: usize, x: T) {
         if let Some(array) = self.array.get_mut(i).unwrap() {
@@ -47,13 +110,14 @@ impl<T: Clone + Eq + PartialEq + Debug + Default + ToString> ChanedHashTable<T>
             *self.array.get_mut(i).unwrap() = Some(array)
         }
     }
-    fn hash(x: T) -> usize {
-        let hash_gen = HashGen::new(Self::z(), Self::w() as u32, Self::d() as u32);
-        hash_gen.hash(x)
+    fn hash(&self, x: &T) -> usize {
+        (self.hasher.hash_one(x) >> (64 - self.d)) as usize
     }
 }
 
-impl<T: Clone + Eq + PartialEq + Debug + Default + ToString> USet<T> for ChanedHashTable<T> {
+impl<T: Clone + Eq + PartialEq + Debug + Default + Hash, S: BuildHasher> USet<T>
+    for ChanedHashTable<T, S>
+{
     fn add(&mut self, x: T) -> bool {
         if self.find(x.clone()) {
             return false;
@@ -61,13 +125,13 @@ impl<T: Clone + Eq + PartialEq + Debug + Default + ToString> USet<T> for ChanedH
         if (self.n + 1) > self.array.len() {
             self.resize();
         }
-        self.set(Self::hash(x.clone()), x);
+        self.set(self.hash(&x), x);
         self.n += 1;
         true
     }
 
     fn remove(&mut self, x: T) -> Option<T> {
-        let j = Self::hash(x.clone());
+        let j = self.hash(&x);
         if self.array.get(j).unwrap().is_none() {
             return None;
         }
@@ -91,6 +155,9 @@ impl<T: Clone + Eq + PartialEq + Debug + Default + ToString> USet<T> for ChanedH
                     .unwrap()
                     .remove(i);
                 self.n -= 1;
+                if 3 * self.n < self.array.len() {
+                    self.resize();
+                }
                 return Some(y);
             }
         }
@@ -100,7 +167,7 @@ impl<T: Clone + Eq + PartialEq + Debug + Default + ToString> USet<T> for ChanedH
         self.n
     }
     fn find(&self, x: T) -> bool {
-        let j = Self::hash(x.clone());
+        let j = self.hash(&x);
         if self.array.get(j).is_none() {
             return false;
         }
@@ -126,13 +193,114 @@ impl<T: Clone + Eq + PartialEq + Debug + Default + ToString> USet<T> for ChanedH
     }
 }
 
+pub struct Iter<'a, T: Clone + Eq + PartialEq + Debug + Default + Hash> {
+    buckets: std::slice::Iter<'a, Option<ArrayStack<T>>>,
+    bucket: Option<array_stack::Iter<'a, T>>,
+}
+impl<'a, T: Clone + Eq + PartialEq + Debug + Default + Hash> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(x) = self.bucket.as_mut().and_then(|bucket| bucket.next()) {
+                return Some(x);
+            }
+            self.bucket = self.buckets.next()?.as_ref().map(|bucket| bucket.iter());
+        }
+    }
+}
+
+pub struct IntoIter<T: Clone + Eq + PartialEq + Debug + Default + Hash> {
+    buckets: std::vec::IntoIter<Option<ArrayStack<T>>>,
+    bucket: Option<array_stack::IntoIter<T>>,
+}
+impl<T: Clone + Eq + PartialEq + Debug + Default + Hash> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(x) = self.bucket.as_mut().and_then(|bucket| bucket.next()) {
+                return Some(x);
+            }
+            self.bucket = self.buckets.next()?.map(|bucket| bucket.into_iter());
+        }
+    }
+}
+
+impl<T: Clone + Eq + PartialEq + Debug + Default + Hash, S: BuildHasher> IntoIterator
+    for ChanedHashTable<T, S>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            buckets: self.array.into_vec().into_iter(),
+            bucket: None,
+        }
+    }
+}
+impl<'a, T: Clone + Eq + PartialEq + Debug + Default + Hash, S: BuildHasher> IntoIterator
+    for &'a ChanedHashTable<T, S>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Eq + PartialEq + Debug + Default + Hash, S: BuildHasher + Default> FromIterator<T>
+    for ChanedHashTable<T, S>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut hash_table = ChanedHashTable::with_hasher(S::default());
+        hash_table.extend(iter);
+        hash_table
+    }
+}
+impl<T: Clone + Eq + PartialEq + Debug + Default + Hash, S: BuildHasher> Extend<T>
+    for ChanedHashTable<T, S>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod chaned_hash_table_test {
 
+    use super::super::hash_gen::{SipHash, Tabulation};
     use super::*;
     #[test]
     fn hash_test() {
-        assert_eq!(ChanedHashTable::<i32>::hash(42), 30);
+        let hash_table = (0..200).collect::<ChanedHashTable<i32>>();
+        assert_eq!(hash_table.capacity(), 256);
+        assert_eq!(
+            hash_table.hash(&42),
+            (HashGen::default().hash_one(42) >> 56) as usize
+        );
+    }
+    #[test]
+    fn hasher_test() {
+        // same-length strings all collided when keys were hashed through to_string
+        let words = ["ab", "cd", "ef", "gh", "ij", "kl", "mn", "op"];
+        let hash_table = words.iter().copied().collect::<ChanedHashTable<_>>();
+        let buckets = words
+            .iter()
+            .map(|word| hash_table.hash(word))
+            .collect::<std::collections::BTreeSet<_>>();
+        assert!(buckets.len() > 1);
+        let mut tabulation = ChanedHashTable::with_hasher(Tabulation::with_seed(1));
+        let mut sip_hash = (0..100).collect::<ChanedHashTable<_, SipHash>>();
+        for word in words {
+            assert!(tabulation.add(word.to_string()));
+        }
+        assert!(!tabulation.add("ab".to_string()));
+        assert!(tabulation.find("kl".to_string()));
+        assert_eq!(tabulation.remove("op".to_string()), Some("op".to_string()));
+        assert_eq!(tabulation.size(), 7);
+        assert!(sip_hash.find(99));
+        assert_eq!(sip_hash.remove(42), Some(42));
+        assert!(!sip_hash.find(42));
     }
 
     #[test]
@@ -152,4 +320,91 @@ mod chaned_hash_table_test {
         assert_eq!(hash_table.find(3), true);
         assert_eq!(hash_table.find(10), false);
     }
+    #[test]
+    fn iter_test() {
+        let mut hash_table = (0..5).collect::<ChanedHashTable<_>>();
+        hash_table.extend(3..8);
+        let mut values = hash_table.iter().copied().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, (0..8).collect::<Vec<_>>());
+        assert_eq!((&hash_table).into_iter().count(), 8);
+        let mut values = hash_table.into_iter().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, (0..8).collect::<Vec<_>>());
+    }
+    #[test]
+    fn resize_test() {
+        let mut hash_table = ChanedHashTable::new();
+        assert_eq!(hash_table.capacity(), 2);
+        for i in 0..1000 {
+            hash_table.add(i);
+            assert!(hash_table.size() <= hash_table.capacity());
+            assert_eq!(
+                hash_table.capacity().trailing_zeros() as usize,
+                hash_table.d
+            );
+        }
+        assert_eq!(hash_table.capacity(), 1024);
+        assert!((0..1000).all(|i| hash_table.find(i)));
+        for i in 0..990 {
+            assert_eq!(hash_table.remove(i), Some(i));
+            assert!(3 * hash_table.size() >= hash_table.capacity());
+        }
+        assert_eq!(hash_table.capacity(), 16);
+        assert!(hash_table.load_factor() >= 1.0 / 3.0);
+        assert!((990..1000).all(|i| hash_table.find(i)));
+        assert!(!hash_table.find(0));
+    }
+    #[test]
+    fn bucket_lens_test() {
+        let n = 100_000;
+        for hash_table in [
+            (0..n).collect::<ChanedHashTable<_>>(),
+            (0..n).map(|i| i * 4096).collect::<ChanedHashTable<_>>(),
+        ] {
+            let bucket_lens = hash_table.chain_lens().collect::<Vec<_>>();
+            assert_eq!(bucket_lens.len(), 131072);
+            assert_eq!(bucket_lens.iter().sum::<usize>(), n);
+            assert!(*bucket_lens.iter().max().unwrap() <= 8);
+        }
+        let hash_table = (0..n)
+            .map(|i| i.to_string())
+            .collect::<ChanedHashTable<_, Tabulation>>();
+        let bucket_lens = hash_table.chain_lens().collect::<Vec<_>>();
+        let sum_of_squares = bucket_lens.iter().map(|len| len * len).sum::<usize>();
+        // a uniform hash gives E[sum len^2] = n (1 + (n - 1) / 2^d)
+        assert!((sum_of_squares as f64) < 1.2 * n as f64 * (1.0 + n as f64 / 131072.0));
+        assert!(*bucket_lens.iter().max().unwrap() <= 12);
+        let empty = bucket_lens.iter().filter(|&&len| len == 0).count();
+        assert!(empty < bucket_lens.len() / 2);
+    }
+    #[test]
+    fn stats_test() {
+        let mut hash_table = ChanedHashTable::new();
+        let stats = hash_table.stats();
+        assert_eq!(stats.histogram, vec![2]);
+        assert_eq!(stats.resizes, 0);
+        hash_table.extend(0..100);
+        let stats = hash_table.stats();
+        assert_eq!(stats.size, 100);
+        assert_eq!(stats.capacity, 128);
+        assert_eq!(stats.load_factor, 100.0 / 128.0);
+        assert_eq!(stats.histogram.iter().sum::<usize>(), 128);
+        assert_eq!(
+            stats
+                .histogram
+                .iter()
+                .enumerate()
+                .map(|(len, count)| len * count)
+                .sum::<usize>(),
+            100
+        );
+        assert_eq!(stats.tombstones, 0);
+        assert_eq!(stats.resizes, 6);
+        // keys that only differ above bit 32 still spread over the buckets
+        let hash_table = (0..100_u64)
+            .map(|i| i << 32)
+            .collect::<ChanedHashTable<_>>();
+        assert!(hash_table.stats().histogram.len() < 8);
+    }
 }
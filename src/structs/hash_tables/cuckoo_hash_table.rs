@@ -0,0 +1,369 @@
+use std::{
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+    iter::{Chain, Flatten},
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::interfaces::{random_generator::RandomGenerator, uset::USet};
+
+use super::hash_gen::HashGen;
+
+/// Cuckoo hashing: two tables of `2^d` slots, each with its own `HashGen`.
+/// `x` lives either at `tables[0][h0(x)]` or at `tables[1][h1(x)]`, so
+/// `find` and `remove` look at two slots at most, plus the `stash`.
+///
+/// Every `HashGen` hashes the same folded word of a key, so keys that fold
+/// alike collide under every choice of hash functions. When reseeding and
+/// growing the tables cannot place a key, it goes in the small `stash`
+/// instead of retrying forever.
+#[derive(Debug)]
+pub struct CuckooHashTable<T: Clone + Debug + Eq + PartialEq + Hash, R: RandomGenerator = StdRng> {
+    tables: [Box<[Option<T>]>; 2],
+    hashers: [HashGen; 2],
+    stash: Vec<T>,
+    n: usize,
+    d: u32,
+    rand_gen: R,
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Hash> CuckooHashTable<T> {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    #[allow(unused)]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Hash, R: RandomGenerator> CuckooHashTable<T, R> {
+    #[allow(unused)]
+    pub fn with_rng(mut rand_gen: R) -> Self {
+        let d = 1;
+        let hashers = Self::gen_hashers(&mut rand_gen, d);
+        Self {
+            tables: [Self::empty_table(d), Self::empty_table(d)],
+            hashers,
+            stash: Vec::new(),
+            n: 0,
+            d,
+            rand_gen,
+        }
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(
+            self.tables[0]
+                .iter()
+                .chain(self.tables[1].iter())
+                .flatten()
+                .chain(self.stash.iter()),
+        )
+    }
+    /// The number of slots over both tables.
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        2 << self.d
+    }
+    fn empty_table(d: u32) -> Box<[Option<T>]> {
+        vec![None; 1 << d].into_boxed_slice()
+    }
+    fn gen_hashers(rand_gen: &mut R, d: u32) -> [HashGen; 2] {
+        [
+            HashGen::new(rand_gen.gen_rand() | 1, 64, d),
+            HashGen::new(rand_gen.gen_rand() | 1, 64, d),
+        ]
+    }
+    fn hash(&self, i: usize, x: &T) -> usize {
+        (self.hashers[i].hash_one(x) >> (64 - self.d)) as usize
+    }
+    fn locate(&self, x: &T) -> Option<(usize, usize)> {
+        (0..2)
+            .map(|i| (i, self.hash(i, x)))
+            .find(|&(i, j)| self.tables[i][j].as_ref() == Some(x))
+    }
+    fn contains(&self, x: &T) -> bool {
+        self.locate(x).is_some() || self.stash.contains(x)
+    }
+    /// An eviction chain longer than this is taken to be a cycle.
+    fn max_loop(&self) -> u32 {
+        6 * self.d + 6
+    }
+    /// Places `x`, evicting occupants to their other table in turn. Returns
+    /// the key left without a slot if the chain runs into a cycle.
+    fn insert(&mut self, mut x: T) -> Result<(), T> {
+        let mut i = 0;
+        for _ in 0..self.max_loop() {
+            let j = self.hash(i, &x);
+            match self.tables[i][j].replace(x) {
+                None => return Ok(()),
+                Some(y) => x = y,
+            }
+            i = 1 - i;
+        }
+        Err(x)
+    }
+    /// Rebuilds both tables with `2^d` slots each and fresh hash functions,
+    /// picking new ones until every key finds a slot. After `MAX_ATTEMPTS / 2`
+    /// failures the tables are doubled once; keys still left over after
+    /// `MAX_ATTEMPTS` go in the stash.
+    fn rehash(&mut self, mut d: u32, pending: Option<T>) {
+        let xs = self
+            .tables
+            .iter_mut()
+            .flat_map(|table| table.iter_mut().filter_map(|slot| slot.take()))
+            .chain(self.stash.drain(..))
+            .chain(pending)
+            .collect::<Vec<_>>();
+        for attempt in 1..=MAX_ATTEMPTS {
+            if attempt == MAX_ATTEMPTS / 2 + 1 {
+                d += 1;
+            }
+            self.d = d;
+            self.tables = [Self::empty_table(d), Self::empty_table(d)];
+            self.hashers = Self::gen_hashers(&mut self.rand_gen, d);
+            let mut leftover = Vec::new();
+            for x in xs.iter() {
+                if let Err(y) = self.insert(x.clone()) {
+                    leftover.push(y);
+                    if attempt < MAX_ATTEMPTS {
+                        break;
+                    }
+                }
+            }
+            if leftover.is_empty() || attempt == MAX_ATTEMPTS {
+                self.stash = leftover;
+                return;
+            }
+        }
+    }
+    /// The smallest `d` keeping `n` keys within a quarter of the slots.
+    fn fit(n: usize) -> u32 {
+        let mut d = 1;
+        while (1 << d) < 2 * n {
+            d += 1;
+        }
+        d
+    }
+}
+
+/// How many sets of hash functions `rehash` tries before using the stash.
+const MAX_ATTEMPTS: u32 = 6;
+
+impl<T: Clone + Debug + Eq + PartialEq + Hash, R: RandomGenerator> USet<T>
+    for CuckooHashTable<T, R>
+{
+    fn size(&self) -> usize {
+        self.n
+    }
+    fn add(&mut self, x: T) -> bool {
+        if self.contains(&x) {
+            return false;
+        }
+        if self.n + 1 > 1 << self.d {
+            self.rehash(Self::fit(self.n + 1), None);
+        }
+        if let Err(y) = self.insert(x) {
+            self.rehash(self.d, Some(y));
+        }
+        self.n += 1;
+        true
+    }
+    fn remove(&mut self, x: T) -> Option<T> {
+        let y = match self.locate(&x) {
+            Some((i, j)) => self.tables[i][j].take(),
+            None => {
+                let k = self.stash.iter().position(|y| *y == x)?;
+                Some(self.stash.swap_remove(k))
+            }
+        };
+        self.n -= 1;
+        if self.d > 1 && 8 * self.n < 1 << self.d {
+            self.rehash(Self::fit(self.n), None);
+        }
+        y
+    }
+    fn find(&self, x: T) -> bool {
+        self.contains(&x)
+    }
+}
+
+type Slots<I, J> = Chain<Flatten<Chain<I, I>>, J>;
+
+pub struct Iter<'a, T: Clone + Debug + Eq + PartialEq + Hash>(
+    Slots<std::slice::Iter<'a, Option<T>>, std::slice::Iter<'a, T>>,
+);
+impl<'a, T: Clone + Debug + Eq + PartialEq + Hash> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug + Eq + PartialEq + Hash>(
+    Slots<std::vec::IntoIter<Option<T>>, std::vec::IntoIter<T>>,
+);
+impl<T: Clone + Debug + Eq + PartialEq + Hash> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Hash, R: RandomGenerator> IntoIterator
+    for CuckooHashTable<T, R>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        let [first, second] = self.tables;
+        IntoIter(
+            first
+                .into_vec()
+                .into_iter()
+                .chain(second.into_vec())
+                .flatten()
+                .chain(self.stash),
+        )
+    }
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq + Hash, R: RandomGenerator> IntoIterator
+    for &'a CuckooHashTable<T, R>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + Hash> FromIterator<T> for CuckooHashTable<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut hash_table = CuckooHashTable::new();
+        hash_table.extend(iter);
+        hash_table
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + Hash, R: RandomGenerator> Extend<T>
+    for CuckooHashTable<T, R>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod cuckoo_hash_table_test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+    use std::collections::HashSet;
+    #[test]
+    fn test() {
+        let mut hash_table = CuckooHashTable::with_seed(1);
+        assert!(hash_table.add(0));
+        assert!(hash_table.add(1));
+        assert!(!hash_table.add(1));
+        assert!(hash_table.find(0));
+        assert!(hash_table.find(1));
+        assert!(!hash_table.find(2));
+        assert_eq!(hash_table.remove(1), Some(1));
+        assert_eq!(hash_table.remove(1), None);
+        assert_eq!(hash_table.size(), 1);
+    }
+    #[test]
+    fn cycle_test() {
+        let mut hash_table = CuckooHashTable::with_seed(1);
+        // z = 1 sends every small key to slot 0 of both tables, so the third
+        // key starts an eviction cycle
+        hash_table.d = 2;
+        hash_table.hashers = [HashGen::new(1, 64, 2), HashGen::new(1, 64, 2)];
+        hash_table.tables = [
+            CuckooHashTable::<i32>::empty_table(2),
+            CuckooHashTable::<i32>::empty_table(2),
+        ];
+        assert!(hash_table.add(10));
+        assert!(hash_table.add(11));
+        assert!(hash_table.add(12));
+        assert_ne!(hash_table.hashers[0], HashGen::new(1, 64, 2));
+        assert!((10..13).all(|x| hash_table.find(x)));
+        assert_eq!(hash_table.size(), 3);
+    }
+    #[test]
+    fn fold_collision_test() {
+        // each key folds to 12345, so no choice of hash functions separates
+        // them and the third one has to go in the stash
+        let keys = (0..3_u64)
+            .map(|k| {
+                let high = 12345_u64.wrapping_sub(k.wrapping_mul(0x100000001b3));
+                (high as u128) << 64 | k as u128
+            })
+            .collect::<Vec<_>>();
+        let mut hash_table = CuckooHashTable::with_seed(1);
+        for &x in keys.iter() {
+            assert!(hash_table.add(x));
+        }
+        assert!(!hash_table.add(keys[2]));
+        assert_eq!(hash_table.stash.len(), 1);
+        assert!(keys.iter().all(|&x| hash_table.find(x)));
+        assert_eq!(hash_table.iter().count(), 3);
+        for x in 0..100 {
+            hash_table.add(x);
+        }
+        assert!(keys.iter().all(|&x| hash_table.find(x)));
+        assert_eq!(hash_table.remove(keys[0]), Some(keys[0]));
+        assert_eq!(hash_table.remove(keys[2]), Some(keys[2]));
+        assert_eq!(hash_table.remove(keys[2]), None);
+        assert!(hash_table.find(keys[1]));
+        assert_eq!(hash_table.size(), 101);
+        assert_eq!(hash_table.into_iter().count(), 101);
+    }
+    #[test]
+    fn resize_test() {
+        let mut hash_table = CuckooHashTable::with_seed(2);
+        for i in 0..1000 {
+            hash_table.add(i);
+            assert!(2 * hash_table.size() <= hash_table.capacity());
+        }
+        assert!((0..1000).all(|i| hash_table.find(i)));
+        for i in 0..990 {
+            assert_eq!(hash_table.remove(i), Some(i));
+        }
+        assert_eq!(hash_table.capacity(), 64);
+        assert!((990..1000).all(|i| hash_table.find(i)));
+    }
+    #[test]
+    fn random_test() {
+        let mut rng = thread_rng();
+        let mut hash_table = CuckooHashTable::new();
+        let mut tobe = HashSet::new();
+        for _ in 0..10000 {
+            let x = rng.gen_range(0..2000);
+            match rng.gen_range(0..3) {
+                0 | 1 => assert_eq!(hash_table.add(x), tobe.insert(x)),
+                _ => assert_eq!(hash_table.remove(x), tobe.take(&x)),
+            }
+            assert_eq!(hash_table.size(), tobe.len());
+            assert_eq!(hash_table.find(x), tobe.contains(&x));
+        }
+        let mut values = hash_table.iter().copied().collect::<Vec<_>>();
+        values.sort();
+        let mut expected = tobe.into_iter().collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(values, expected);
+        assert_eq!(hash_table.into_iter().count(), expected.len());
+    }
+    #[test]
+    fn string_test() {
+        let mut hash_table = ["ab", "cd", "ef"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<CuckooHashTable<_>>();
+        assert!(hash_table.find("cd".to_string()));
+        assert_eq!(hash_table.remove("ab".to_string()), Some("ab".to_string()));
+        assert_eq!((&hash_table).into_iter().count(), 2);
+    }
+}
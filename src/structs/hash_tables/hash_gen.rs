@@ -1,6 +1,26 @@
-use std::{fmt::Debug, ops::Div};
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hasher},
+    ops::Div,
+    sync::Arc,
+};
 
-#[derive(Debug)]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// SipHash, as used by `std::collections::HashMap`, keyed randomly per
+/// instance.
+#[allow(unused)]
+pub type SipHash = RandomState;
+
+/// Multiplicative hashing: `hash(x) = ((z * x) mod 2^w) div 2^(w - d)`.
+///
+/// As a `BuildHasher` the result is returned in the top `w` bits of the
+/// `u64`, so a table of size `2^d` takes the top `d` bits of `finish()`.
+/// With `w < 64` only the low `w` bits of the folded key reach the hash.
+/// The default is therefore `w = 64`, with `z` the odd 64-bit golden ratio
+/// constant.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HashGen {
     z: usize,
     w: u32,
@@ -9,31 +29,197 @@ pub struct HashGen {
 
 impl HashGen {
     pub fn new(z: usize, w: u32, d: u32) -> Self {
+        if w == 0 || w > 64 || d > w {
+            panic!("invalid hash width w = {}, d = {}", w, d)
+        }
         Self { z, w, d }
     }
+    /// A `HashGen` over 64-bit words with a random odd multiplier.
+    #[allow(unused)]
+    pub fn with_seed(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self::new(rng.gen::<usize>() | 1, 64, 8)
+    }
     pub fn from_usize(&self, x: usize) -> usize {
-        ((self.z * x) % (2_i64.pow(self.w as u32) as usize))
-            .div(2_i32.pow((self.w - self.d) as u32) as usize)
-    }
-    pub fn hash<T: Clone + Debug + ToString>(&self, x: T) -> usize {
-        let str = x.to_string();
-        match str.parse::<usize>() {
-            Ok(num) => self.from_usize(num),
-            Err(_) => {
-                let bytes = str.bytes().len();
-                self.from_usize(bytes)
-            }
+        (self.mul(x as u64) as usize).div(2_u64.pow(self.w - self.d) as usize)
+    }
+    fn mul(&self, x: u64) -> u64 {
+        let zx = (self.z as u64).wrapping_mul(x);
+        if self.w == 64 {
+            zx
+        } else {
+            zx % 2_u64.pow(self.w)
+        }
+    }
+}
+impl Default for HashGen {
+    fn default() -> Self {
+        Self::new(0x9e3779b97f4a7c15, 64, 8)
+    }
+}
+impl BuildHasher for HashGen {
+    type Hasher = HashGenHasher;
+    fn build_hasher(&self) -> Self::Hasher {
+        HashGenHasher {
+            hash_gen: self.clone(),
+            x: Fold::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HashGenHasher {
+    hash_gen: HashGen,
+    x: Fold,
+}
+impl Hasher for HashGenHasher {
+    fn finish(&self) -> u64 {
+        self.hash_gen.mul(self.x.0) << (64 - self.hash_gen.w)
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        self.x.write(bytes)
+    }
+    fn write_u8(&mut self, i: u8) {
+        self.x.push(i as u64)
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.x.push(i as u64)
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.x.push(i as u64)
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.x.push(i)
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.x.push(i as u64)
+    }
+}
+
+/// Simple tabulation hashing: the key is split into 8 bytes and each byte
+/// indexes its own table of random words, which are xor-ed together.
+#[derive(Debug, Clone)]
+pub struct Tabulation {
+    tables: Arc<[[u64; 256]; 8]>,
+}
+
+impl Tabulation {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    #[allow(unused)]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+    fn with_rng(mut rng: StdRng) -> Self {
+        let mut tables = [[0; 256]; 8];
+        for table in tables.iter_mut() {
+            rng.fill(&mut table[..]);
+        }
+        Self {
+            tables: Arc::new(tables),
+        }
+    }
+}
+impl Default for Tabulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl BuildHasher for Tabulation {
+    type Hasher = TabulationHasher;
+    fn build_hasher(&self) -> Self::Hasher {
+        TabulationHasher {
+            tables: self.tables.clone(),
+            x: Fold::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TabulationHasher {
+    tables: Arc<[[u64; 256]; 8]>,
+    x: Fold,
+}
+impl Hasher for TabulationHasher {
+    fn finish(&self) -> u64 {
+        self.x
+            .0
+            .to_le_bytes()
+            .iter()
+            .zip(self.tables.iter())
+            .fold(0, |h, (&byte, table)| h ^ table[byte as usize])
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        self.x.write(bytes)
+    }
+    fn write_u8(&mut self, i: u8) {
+        self.x.push(i as u64)
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.x.push(i as u64)
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.x.push(i as u64)
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.x.push(i)
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.x.push(i as u64)
+    }
+}
+
+/// Folds everything written to a hasher into one word. A single integer is
+/// kept as is, so `hash(x)` for an integer key hashes `x` itself.
+#[derive(Debug, Default)]
+struct Fold(u64);
+
+impl Fold {
+    fn push(&mut self, i: u64) {
+        self.0 = self.0.wrapping_mul(0x100000001b3).wrapping_add(i);
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.push(u64::from_le_bytes(word));
         }
     }
 }
 
 #[cfg(test)]
 mod hash_gen_test {
-    use super::HashGen;
+    use super::*;
 
     #[test]
     fn test() {
         let hash_gen = HashGen::new(4102541685, 32, 8);
         assert_eq!(hash_gen.from_usize(42), 30);
+        assert_eq!(hash_gen.hash_one(42) >> 56, 30);
+        assert_eq!(hash_gen.hash_one(42_u8), hash_gen.hash_one(42_i64));
+    }
+    #[test]
+    fn build_hasher_test() {
+        let hash_gen = HashGen::with_seed(1);
+        assert_eq!(hash_gen.hash_one("abc"), hash_gen.hash_one("abc"));
+        assert_ne!(hash_gen.hash_one("abc"), hash_gen.hash_one("abd"));
+        let tabulation = Tabulation::with_seed(1);
+        assert_eq!(tabulation.hash_one("abc"), tabulation.hash_one("abc"));
+        assert_ne!(tabulation.hash_one("abc"), tabulation.hash_one("abd"));
+        assert_eq!(tabulation.hash_one(7), Tabulation::with_seed(1).hash_one(7));
+        let hash_gen = HashGen::default();
+        assert_ne!(hash_gen.hash_one("abcdefgh"), hash_gen.hash_one("abcdzzzz"));
+        assert_ne!(
+            hash_gen.hash_one("key_aaaa_1"),
+            hash_gen.hash_one("key_bbbb_1")
+        );
+        assert_ne!(
+            hash_gen.hash_one(1_u64 << 32),
+            hash_gen.hash_one(2_u64 << 32)
+        );
+        let sip_hash = SipHash::new();
+        assert_eq!(sip_hash.hash_one("abc"), sip_hash.hash_one("abc"));
     }
 }
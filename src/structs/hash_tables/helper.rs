@@ -0,0 +1,51 @@
+/// Probe lengths of an open-addressing table: how many slots a successful
+/// `find` looks at for each entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProbeStats {
+    /// `histogram[k]` is the number of entries found after `k + 1` probes.
+    pub histogram: Vec<usize>,
+    pub average: f64,
+    pub max: usize,
+}
+
+impl ProbeStats {
+    pub(super) fn from_lens<I: IntoIterator<Item = usize>>(lens: I) -> Self {
+        let mut histogram = Vec::new();
+        let mut total = 0;
+        let mut count = 0;
+        for len in lens {
+            if histogram.len() < len {
+                histogram.resize(len, 0);
+            }
+            histogram[len - 1] += 1;
+            total += len;
+            count += 1;
+        }
+        Self {
+            max: histogram.len(),
+            average: if count == 0 {
+                0.0
+            } else {
+                total as f64 / count as f64
+            },
+            histogram,
+        }
+    }
+}
+
+/// Health of a hash table, for picking its parameters and spotting a hash
+/// function that collides on the keys at hand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HashTableStats {
+    pub size: usize,
+    pub capacity: usize,
+    pub load_factor: f64,
+    /// For `ChanedHashTable`, `histogram[k]` is the number of buckets whose
+    /// chain holds `k` entries. For `LinerHashTable` it is the probe
+    /// histogram of `ProbeStats`.
+    pub histogram: Vec<usize>,
+    /// Slots still marked `Del`; always 0 for a chained table.
+    pub tombstones: usize,
+    /// How many times the table has been rebuilt since it was created.
+    pub resizes: usize,
+}
@@ -1,8 +1,17 @@
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+};
 
-use crate::{interfaces::uset::USet, structs::arrays::array_stack::ArrayStack};
+use crate::{
+    interfaces::uset::USet,
+    structs::arrays::array_stack::{self, ArrayStack},
+};
 
-use super::hash_gen::HashGen;
+use super::{
+    hash_gen::HashGen,
+    helper::{HashTableStats, ProbeStats},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum DataState<T: Clone + Debug + Eq + PartialEq + Default> {
@@ -17,171 +26,235 @@ impl<T: Clone + Debug + Eq + PartialEq + Default> Default for DataState<T> {
 }
 
 #[derive(Debug)]
-pub struct LinerHashTable<T: Clone + Debug + Eq + PartialEq + Default + ToString> {
+pub struct LinerHashTable<
+    T: Clone + Debug + Eq + PartialEq + Default + Hash,
+    S: BuildHasher = HashGen,
+> {
     array: ArrayStack<DataState<T>>,
-    hashgen: HashGen,
+    hasher: S,
     n: usize,
     q: usize,
     d: u32,
+    resizes: usize,
 }
 
-impl<T: Clone + Debug + Eq + PartialEq + Default + ToString> LinerHashTable<T> {
+impl<T: Clone + Debug + Eq + PartialEq + Default + Hash> LinerHashTable<T> {
     pub fn new() -> Self {
-        let d = 8;
-        let len = 2_i32.pow(d) as usize;
-        let array = ArrayStack::new_with_default(len);
+        Self::with_hasher(HashGen::default())
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Default + Hash, S: BuildHasher> LinerHashTable<T, S> {
+    #[allow(unused)]
+    pub fn with_hasher(hasher: S) -> Self {
+        let d = 1;
+        let array = ArrayStack::new_with_default(1 << d);
         Self {
             d,
             array,
-            hashgen: HashGen::new(33, 32, 8),
+            hasher,
             q: 0,
             n: 0,
+            resizes: 0,
         }
     }
+    #[allow(unused)]
+    pub fn hasher(&self) -> &S {
+        &self.hasher
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.array.size()
+    }
+    /// The fraction of slots holding a live entry. Resizing keeps it between
+    /// 1/8 and 1/2, and right after a resize it is about 1/3.
+    #[allow(unused)]
+    pub fn load_factor(&self) -> f64 {
+        self.n as f64 / self.capacity() as f64
+    }
+    fn hash(&self, x: &T) -> usize {
+        (self.hasher.hash_one(x) >> (64 - self.d)) as usize
+    }
+    fn next_index(&self, i: usize) -> usize {
+        (i + 1) & (self.capacity() - 1)
+    }
+    /// Returns the slot holding `x`, or `None` after reaching a `Null` slot.
+    fn find_index(&self, x: &T) -> Option<usize> {
+        let mut i = self.hash(x);
+        loop {
+            match self.array.get_ref(i)? {
+                DataState::Null => return None,
+                DataState::Exist(y) if y == x => return Some(i),
+                _ => i = self.next_index(i),
+            }
+        }
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.array.iter())
+    }
+    /// Rebuilds the table with the smallest `2^d >= 3n` slots, rehashing
+    /// only the live entries so every `Del` tombstone is dropped.
     fn resize(&mut self) {
+        self.resizes += 1;
         let mut d = 1;
-        //while (1 << d) < 3 * self.n {
-        //d += 1;
-        //}
-        //let mut new_array = ArrayStack::new_with_default(1 << d);
-
-        //        for k in 0..self.array.size() {
-        //if let Some(data) = self.array.get(k){
-        //match data {
-        //DataState::Exist(_)=>{
-
-        //}
-        //}
-        //}
-        //}
+        while (1 << d) < 3 * self.n {
+            d += 1;
+        }
+        let old = std::mem::replace(&mut self.array, ArrayStack::new_with_default(1 << d));
+        self.d = d;
+        for data in old {
+            if let DataState::Exist(x) = data {
+                let mut i = self.hash(&x);
+                while let Some(DataState::Exist(_)) = self.array.get_ref(i) {
+                    i = self.next_index(i);
+                }
+                self.array.set(i, DataState::Exist(x));
+            }
+        }
+        self.q = self.n;
+    }
+    #[allow(unused)]
+    pub fn probe_stats(&self) -> ProbeStats {
+        ProbeStats::from_lens(self.probe_lens())
+    }
+    #[allow(unused)]
+    pub fn stats(&self) -> HashTableStats {
+        HashTableStats {
+            size: self.n,
+            capacity: self.capacity(),
+            load_factor: self.load_factor(),
+            histogram: self.probe_stats().histogram,
+            tombstones: self.q - self.n,
+            resizes: self.resizes,
+        }
+    }
+    /// The number of slots probed to find each entry.
+    fn probe_lens(&self) -> Vec<usize> {
+        self.array
+            .iter()
+            .enumerate()
+            .filter_map(|(i, data)| match data {
+                DataState::Exist(x) => {
+                    Some(((i + self.capacity() - self.hash(x)) & (self.capacity() - 1)) + 1)
+                }
+                _ => None,
+            })
+            .collect()
     }
 }
-impl<T: Clone + Debug + Eq + PartialEq + Default + ToString> USet<T> for LinerHashTable<T> {
+impl<T: Clone + Debug + Eq + PartialEq + Default + Hash, S: BuildHasher> USet<T>
+    for LinerHashTable<T, S>
+{
     fn add(&mut self, x: T) -> bool {
-        if self.find(x.clone()) {
+        if self.find_index(&x).is_some() {
             return false;
         }
-        if 2 * (self.q + 1) > self.array.size() {
+        // `q` already counts live entries and `Del` tombstones, so this is
+        // the ODS rule `2(q + 1) > capacity`, not `2(n + q)`: it keeps at
+        // least half the slots `Null` once `x` is in
+        if 2 * (self.q + 1) > self.capacity() {
             self.resize()
         }
-        let mut i = self.hashgen.hash(x.clone());
-        if let Some(mut data) = self.array.get(i) {
-            loop {
-                match data {
-                    DataState::Exist(_) => {
-                        i = if i == (self.array.size() - 1) {
-                            0
-                        } else {
-                            i + 1
-                        };
-                        if let Some(new_data) = self.array.get(i) {
-                            data = new_data
-                        } else {
-                            return false;
-                        }
-                    }
-                    DataState::Del => {
-                        self.array.set(i, DataState::Exist(x.clone()));
-                        self.n += 1;
-                        return true;
-                    }
-                    DataState::Null => {
-                        self.array.set(i, DataState::Exist(x.clone()));
-                        self.n += 1;
-                        self.q += 1;
-                        return true;
-                    }
-                }
-            }
-        } else {
-            false
+        let mut i = self.hash(&x);
+        while let Some(DataState::Exist(_)) = self.array.get_ref(i) {
+            i = self.next_index(i);
+        }
+        if let Some(DataState::Null) = self.array.get_ref(i) {
+            self.q += 1;
         }
+        self.array.set(i, DataState::Exist(x));
+        self.n += 1;
+        true
     }
     fn find(&self, x: T) -> bool {
-        let mut i = self.hashgen.hash(x.clone());
-        if let Some(mut data) = self.array.get(i) {
-            loop {
-                match &data {
-                    DataState::Null => return false,
-                    DataState::Del => {
-                        i = if i == (self.array.size() - 1) {
-                            0
-                        } else {
-                            i + 1
-                        };
-                        if let Some(new_data) = self.array.get(i) {
-                            data = new_data
-                        } else {
-                            return false;
-                        }
-                    }
-                    DataState::Exist(y) => {
-                        if y == &x {
-                            return true;
-                        }
-                        i = if i == (self.array.size() - 1) {
-                            0
-                        } else {
-                            i + 1
-                        };
-                        if let Some(new_data) = self.array.get(i) {
-                            data = new_data
-                        } else {
-                            return false;
-                        }
-                    }
-                }
-            }
-        }
-        false
+        self.find_index(&x).is_some()
     }
     fn remove(&mut self, x: T) -> Option<T> {
-        let mut i = self.hashgen.hash(x.clone());
-        if let Some(mut data) = self.array.get(i) {
-            loop {
-                match &data {
-                    DataState::Null => return None,
-                    DataState::Del => {
-                        i = if i == (self.array.size() - 1) {
-                            0
-                        } else {
-                            i + 1
-                        };
-                        if let Some(new_data) = self.array.get(i) {
-                            data = new_data
-                        } else {
-                            return None;
-                        }
-                    }
-                    DataState::Exist(y) => {
-                        if y == &x {
-                            self.array.set(i, DataState::Del);
-                            self.n -= 1;
-                            return Some(x);
-                        }
-                        i = if i == (self.array.size() - 1) {
-                            0
-                        } else {
-                            i + 1
-                        };
-                        if let Some(new_data) = self.array.get(i) {
-                            data = new_data
-                        } else {
-                            return None;
-                        }
-                    }
-                }
-            }
+        let i = self.find_index(&x)?;
+        let data = std::mem::replace(self.array.get_mut(i)?, DataState::Del);
+        self.n -= 1;
+        if 8 * self.n < self.capacity() {
+            self.resize()
+        }
+        match data {
+            DataState::Exist(y) => Some(y),
+            _ => None,
         }
-        None
     }
     fn size(&self) -> usize {
         self.n
     }
 }
 
+pub struct Iter<'a, T: Clone + Debug + Eq + PartialEq + Default + Hash>(
+    array_stack::Iter<'a, DataState<T>>,
+);
+impl<'a, T: Clone + Debug + Eq + PartialEq + Default + Hash> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let DataState::Exist(x) = self.0.next()? {
+                return Some(x);
+            }
+        }
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug + Eq + PartialEq + Default + Hash>(
+    array_stack::IntoIter<DataState<T>>,
+);
+impl<T: Clone + Debug + Eq + PartialEq + Default + Hash> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let DataState::Exist(x) = self.0.next()? {
+                return Some(x);
+            }
+        }
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Default + Hash, S: BuildHasher> IntoIterator
+    for LinerHashTable<T, S>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.array.into_iter())
+    }
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq + Default + Hash, S: BuildHasher> IntoIterator
+    for &'a LinerHashTable<T, S>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + Default + Hash, S: BuildHasher + Default> FromIterator<T>
+    for LinerHashTable<T, S>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut hash_table = LinerHashTable::with_hasher(S::default());
+        hash_table.extend(iter);
+        hash_table
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + Default + Hash, S: BuildHasher> Extend<T>
+    for LinerHashTable<T, S>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod liner_hash_table_test {
+    use super::super::hash_gen::{SipHash, Tabulation};
     use super::*;
     #[test]
     fn test() {
@@ -201,4 +274,115 @@ mod liner_hash_table_test {
         assert_eq!(liner_hash_table.remove(1), Some(1));
         assert_eq!(liner_hash_table.remove(10), None);
     }
+    #[test]
+    fn iter_test() {
+        let mut liner_hash_table = (0..5).collect::<LinerHashTable<_>>();
+        liner_hash_table.extend(3..8);
+        liner_hash_table.remove(4);
+        let mut values = liner_hash_table.iter().copied().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![0, 1, 2, 3, 5, 6, 7]);
+        assert_eq!((&liner_hash_table).into_iter().count(), 7);
+        let mut values = liner_hash_table.into_iter().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![0, 1, 2, 3, 5, 6, 7]);
+    }
+    #[test]
+    fn hasher_test() {
+        let words = ["ab", "cd", "ef", "gh", "ij", "kl", "mn", "op"];
+        let hash_table = words.iter().copied().collect::<LinerHashTable<_>>();
+        let slots = words
+            .iter()
+            .map(|word| hash_table.hash(word))
+            .collect::<std::collections::BTreeSet<_>>();
+        assert!(slots.len() > 1);
+        let mut tabulation = LinerHashTable::with_hasher(Tabulation::with_seed(1));
+        let mut sip_hash = (0..100).collect::<LinerHashTable<_, SipHash>>();
+        for word in words {
+            assert!(tabulation.add(word.to_string()));
+        }
+        assert!(!tabulation.add("ab".to_string()));
+        assert!(tabulation.find("kl".to_string()));
+        assert_eq!(tabulation.remove("op".to_string()), Some("op".to_string()));
+        assert_eq!(tabulation.size(), 7);
+        assert!(sip_hash.find(99));
+        assert_eq!(sip_hash.remove(42), Some(42));
+        assert!(!sip_hash.find(42));
+    }
+    #[test]
+    fn resize_test() {
+        let mut liner_hash_table = LinerHashTable::new();
+        assert_eq!(liner_hash_table.capacity(), 2);
+        for i in 0..100 {
+            liner_hash_table.add(i);
+            assert!(2 * liner_hash_table.q <= liner_hash_table.capacity());
+        }
+        assert_eq!(liner_hash_table.capacity(), 256);
+        assert!(liner_hash_table.load_factor() <= 0.5);
+        for i in 0..90 {
+            assert_eq!(liner_hash_table.remove(i), Some(i));
+            assert!(8 * liner_hash_table.size() >= liner_hash_table.capacity());
+        }
+        assert_eq!(liner_hash_table.capacity(), 64);
+        assert_eq!(liner_hash_table.q, 15);
+        assert!((90..100).all(|i| liner_hash_table.find(i)));
+        assert!(liner_hash_table.load_factor() > 0.125);
+    }
+    #[test]
+    fn tombstone_test() {
+        let mut liner_hash_table = (0..10).collect::<LinerHashTable<_>>();
+        let capacity = liner_hash_table.capacity();
+        for i in 10..10000 {
+            liner_hash_table.add(i);
+            liner_hash_table.remove(i);
+            assert!(2 * liner_hash_table.q <= capacity);
+        }
+        assert_eq!(liner_hash_table.capacity(), capacity);
+        assert_eq!(liner_hash_table.size(), 10);
+    }
+    #[test]
+    fn million_keys_test() {
+        let n = 1_000_000;
+        let mut liner_hash_table = LinerHashTable::with_hasher(Tabulation::with_seed(7));
+        for i in 0..n {
+            assert!(liner_hash_table.add(i * 7919));
+        }
+        assert_eq!(liner_hash_table.size(), n);
+        let probe_lens = liner_hash_table.probe_lens();
+        let average = probe_lens.iter().sum::<usize>() as f64 / n as f64;
+        assert!(average < 3.0);
+        assert!(probe_lens.iter().max().unwrap() < &64);
+        for i in (0..n).step_by(2) {
+            assert_eq!(liner_hash_table.remove(i * 7919), Some(i * 7919));
+        }
+        let probe_lens = liner_hash_table.probe_lens();
+        let average = probe_lens.iter().sum::<usize>() as f64 / (n / 2) as f64;
+        assert!(average < 3.0);
+        assert!(probe_lens.iter().max().unwrap() < &64);
+        for i in 0..n {
+            assert_eq!(liner_hash_table.find(i * 7919), i % 2 == 1);
+            liner_hash_table.remove(i * 7919);
+        }
+        assert_eq!(liner_hash_table.size(), 0);
+        assert_eq!(liner_hash_table.capacity(), 2);
+    }
+    #[test]
+    fn stats_test() {
+        let mut liner_hash_table = LinerHashTable::new();
+        liner_hash_table.extend(0..100);
+        let stats = liner_hash_table.stats();
+        assert_eq!(stats.size, 100);
+        assert_eq!(stats.capacity, 256);
+        assert_eq!(stats.load_factor, 100.0 / 256.0);
+        assert_eq!(stats.histogram.iter().sum::<usize>(), 100);
+        assert_eq!(stats.tombstones, 0);
+        assert_eq!(stats.resizes, 7);
+        for i in 0..20 {
+            liner_hash_table.remove(i);
+        }
+        let stats = liner_hash_table.stats();
+        assert_eq!(stats.tombstones, 20);
+        assert_eq!(stats.histogram.iter().sum::<usize>(), 80);
+        assert_eq!(stats.resizes, 7);
+    }
 }
@@ -0,0 +1,316 @@
+use std::{
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::interfaces::uset::USet;
+
+use super::{hash_gen::HashGen, helper::ProbeStats};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry<T: Clone + Debug + Eq + PartialEq + Hash> {
+    x: T,
+    /// How far the entry sits from its home slot `hash(x)`.
+    dist: usize,
+}
+
+/// Linear probing where an entry being placed takes the slot of any entry
+/// closer to its own home ("takes from the rich"), which keeps probe lengths
+/// even. Removal shifts the following entries back instead of leaving
+/// tombstones.
+#[derive(Debug)]
+pub struct RobinHoodHashTable<T: Clone + Debug + Eq + PartialEq + Hash, S: BuildHasher = HashGen> {
+    array: Box<[Option<Entry<T>>]>,
+    hasher: S,
+    n: usize,
+    d: u32,
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Hash> RobinHoodHashTable<T> {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self::with_hasher(HashGen::default())
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Hash, S: BuildHasher> RobinHoodHashTable<T, S> {
+    #[allow(unused)]
+    pub fn with_hasher(hasher: S) -> Self {
+        let d = 1;
+        Self {
+            array: Self::empty_array(d),
+            hasher,
+            n: 0,
+            d,
+        }
+    }
+    #[allow(unused)]
+    pub fn hasher(&self) -> &S {
+        &self.hasher
+    }
+    #[allow(unused)]
+    pub fn capacity(&self) -> usize {
+        self.array.len()
+    }
+    /// The fraction of slots holding an entry, kept between 1/8 and 1/2.
+    #[allow(unused)]
+    pub fn load_factor(&self) -> f64 {
+        self.n as f64 / self.capacity() as f64
+    }
+    #[allow(unused)]
+    pub fn probe_stats(&self) -> ProbeStats {
+        ProbeStats::from_lens(self.array.iter().flatten().map(|entry| entry.dist + 1))
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.array.iter())
+    }
+    fn empty_array(d: u32) -> Box<[Option<Entry<T>>]> {
+        vec![None; 1 << d].into_boxed_slice()
+    }
+    fn hash(&self, x: &T) -> usize {
+        (self.hasher.hash_one(x) >> (64 - self.d)) as usize
+    }
+    fn next_index(&self, i: usize) -> usize {
+        (i + 1) & (self.capacity() - 1)
+    }
+    /// Returns the slot holding `x`. The search stops early at an entry
+    /// closer to its home than `x` would be, since `add` would have put `x`
+    /// there.
+    fn find_index(&self, x: &T) -> Option<usize> {
+        let mut i = self.hash(x);
+        let mut dist = 0;
+        loop {
+            let entry = self.array[i].as_ref()?;
+            if entry.dist < dist {
+                return None;
+            }
+            if &entry.x == x {
+                return Some(i);
+            }
+            i = self.next_index(i);
+            dist += 1;
+        }
+    }
+    fn insert(&mut self, x: T) {
+        let mut i = self.hash(&x);
+        let mut entry = Entry { x, dist: 0 };
+        loop {
+            match &mut self.array[i] {
+                None => {
+                    self.array[i] = Some(entry);
+                    return;
+                }
+                Some(rich) if rich.dist < entry.dist => std::mem::swap(rich, &mut entry),
+                _ => {}
+            }
+            i = self.next_index(i);
+            entry.dist += 1;
+        }
+    }
+    /// Rebuilds the table with the smallest `2^d >= 3n` slots.
+    fn resize(&mut self) {
+        let mut d = 1;
+        while (1 << d) < 3 * self.n {
+            d += 1;
+        }
+        let old = std::mem::replace(&mut self.array, Self::empty_array(d));
+        self.d = d;
+        for entry in old.into_vec().into_iter().flatten() {
+            self.insert(entry.x);
+        }
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Hash, S: BuildHasher> USet<T>
+    for RobinHoodHashTable<T, S>
+{
+    fn size(&self) -> usize {
+        self.n
+    }
+    fn add(&mut self, x: T) -> bool {
+        if self.find_index(&x).is_some() {
+            return false;
+        }
+        if 2 * (self.n + 1) > self.capacity() {
+            self.resize();
+        }
+        self.insert(x);
+        self.n += 1;
+        true
+    }
+    fn remove(&mut self, x: T) -> Option<T> {
+        let mut i = self.find_index(&x)?;
+        let removed = self.array[i].take();
+        // shift the rest of the cluster back until an empty slot or an entry
+        // already at home
+        let mut j = self.next_index(i);
+        while let Some(mut entry) = self.array[j].take_if(|entry| entry.dist > 0) {
+            entry.dist -= 1;
+            self.array[i] = Some(entry);
+            i = j;
+            j = self.next_index(j);
+        }
+        self.n -= 1;
+        if 8 * self.n < self.capacity() {
+            self.resize();
+        }
+        removed.map(|entry| entry.x)
+    }
+    fn find(&self, x: T) -> bool {
+        self.find_index(&x).is_some()
+    }
+}
+
+pub struct Iter<'a, T: Clone + Debug + Eq + PartialEq + Hash>(
+    std::slice::Iter<'a, Option<Entry<T>>>,
+);
+impl<'a, T: Clone + Debug + Eq + PartialEq + Hash> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.0.next()? {
+                return Some(&entry.x);
+            }
+        }
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug + Eq + PartialEq + Hash>(std::vec::IntoIter<Option<Entry<T>>>);
+impl<T: Clone + Debug + Eq + PartialEq + Hash> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.0.next()? {
+                return Some(entry.x);
+            }
+        }
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq + Hash, S: BuildHasher> IntoIterator
+    for RobinHoodHashTable<T, S>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.array.into_vec().into_iter())
+    }
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq + Hash, S: BuildHasher> IntoIterator
+    for &'a RobinHoodHashTable<T, S>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + Hash, S: BuildHasher + Default> FromIterator<T>
+    for RobinHoodHashTable<T, S>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut hash_table = RobinHoodHashTable::with_hasher(S::default());
+        hash_table.extend(iter);
+        hash_table
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq + Hash, S: BuildHasher> Extend<T>
+    for RobinHoodHashTable<T, S>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod robin_hood_hash_table_test {
+    use super::super::{hash_gen::Tabulation, liner_hash_table::LinerHashTable};
+    use super::*;
+    use rand::{thread_rng, Rng};
+    use std::collections::HashSet;
+
+    fn check_invariants<T: Clone + Debug + Eq + PartialEq + Hash, S: BuildHasher>(
+        hash_table: &RobinHoodHashTable<T, S>,
+    ) {
+        let capacity = hash_table.capacity();
+        for (i, slot) in hash_table.array.iter().enumerate() {
+            if let Some(entry) = slot {
+                let home = hash_table.hash(&entry.x);
+                assert_eq!(entry.dist, (i + capacity - home) & (capacity - 1));
+                // an entry is never poorer than the one before it by more than a slot
+                if entry.dist > 0 {
+                    let prev = hash_table.array[(i + capacity - 1) & (capacity - 1)].as_ref();
+                    assert!(prev.unwrap().dist + 1 >= entry.dist);
+                }
+            }
+        }
+        assert_eq!(hash_table.array.iter().flatten().count(), hash_table.size());
+    }
+    #[test]
+    fn test() {
+        let mut hash_table = RobinHoodHashTable::new();
+        assert!(hash_table.add(0));
+        assert!(hash_table.add(1));
+        assert!(!hash_table.add(1));
+        assert!(hash_table.find(0));
+        assert!(!hash_table.find(2));
+        assert_eq!(hash_table.remove(1), Some(1));
+        assert_eq!(hash_table.remove(1), None);
+        assert_eq!(hash_table.size(), 1);
+        check_invariants(&hash_table);
+    }
+    #[test]
+    fn backward_shift_test() {
+        let mut hash_table = RobinHoodHashTable::with_hasher(Tabulation::with_seed(1));
+        hash_table.extend(0..1000);
+        let capacity = hash_table.capacity();
+        for i in 0..500 {
+            assert_eq!(hash_table.remove(i), Some(i));
+            check_invariants(&hash_table);
+        }
+        assert_eq!(hash_table.capacity(), capacity);
+        assert!((0..500).all(|i| !hash_table.find(i)));
+        assert!((500..1000).all(|i| hash_table.find(i)));
+    }
+    #[test]
+    fn random_test() {
+        let mut rng = thread_rng();
+        let mut hash_table = RobinHoodHashTable::new();
+        let mut tobe = HashSet::new();
+        for _ in 0..10000 {
+            let x = rng.gen_range(0..2000);
+            match rng.gen_range(0..3) {
+                0 | 1 => assert_eq!(hash_table.add(x), tobe.insert(x)),
+                _ => assert_eq!(hash_table.remove(x), tobe.take(&x)),
+            }
+            assert_eq!(hash_table.size(), tobe.len());
+            assert_eq!(hash_table.find(x), tobe.contains(&x));
+        }
+        check_invariants(&hash_table);
+        let mut values = hash_table.iter().copied().collect::<Vec<_>>();
+        values.sort();
+        let mut expected = tobe.into_iter().collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(values, expected);
+        assert_eq!(hash_table.into_iter().count(), expected.len());
+    }
+    #[test]
+    fn probe_stats_test() {
+        let n = 100_000;
+        let robin_hood = (0..n).collect::<RobinHoodHashTable<_, Tabulation>>();
+        let mut liner = LinerHashTable::with_hasher(robin_hood.hasher().clone());
+        liner.extend(0..n);
+        assert_eq!(robin_hood.capacity(), liner.capacity());
+        let robin_hood_stats = robin_hood.probe_stats();
+        let liner_stats = liner.probe_stats();
+        // same keys in the same slots overall, so the total probe length
+        // matches and only its spread differs
+        assert_eq!(robin_hood_stats.average, liner_stats.average);
+        assert!(robin_hood_stats.max <= liner_stats.max);
+        assert_eq!(robin_hood_stats.histogram.iter().sum::<usize>(), n);
+        assert_eq!(robin_hood_stats.histogram.len(), robin_hood_stats.max);
+    }
+}
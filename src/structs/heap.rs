@@ -1,2 +1,3 @@
 pub mod binary_heap;
+pub mod indexed_binary_heap;
 pub mod meldable_heap;
@@ -1,17 +1,57 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, marker::PhantomData, ops::Range};
 
+use crate::interfaces::priority_queue::{HeapOrder, MaxOrder, MinOrder, PriorityQueue};
+
+/// A d-ary heap in an array, with the children of `i` at `d i + 1` to
+/// `d i + d`. A larger `d` makes the tree shallower, so `add` does fewer
+/// swaps and `remove` touches fewer cache lines per level while comparing
+/// more children. `O` decides whether the smallest or the largest element
+/// is at the root.
 #[derive(Debug, PartialEq)]
-pub struct BinaryHeap<T: Clone + Debug + PartialEq + PartialOrd> {
+pub struct BinaryHeap<T: Clone + Debug + PartialEq + PartialOrd, O: HeapOrder = MinOrder> {
     array: Box<[Option<T>]>,
     n: usize,
+    d: usize,
+    _order: PhantomData<O>,
 }
 
 impl<T: Clone + Debug + PartialEq + PartialOrd> BinaryHeap<T> {
     pub fn new() -> Self {
-        BinaryHeap {
-            array: Box::new([]),
-            n: 0,
-        }
+        Self::with_order(MinOrder)
+    }
+    /// A min-heap in which every node has up to `d` children.
+    #[allow(unused)]
+    pub fn with_arity(d: usize) -> Self {
+        Self::with_arity_and_order(d, MinOrder)
+    }
+    /// Builds a binary min-heap over `array` in O(n).
+    #[allow(unused)]
+    pub fn from_vec(array: Vec<T>) -> Self {
+        Self::from_vec_with(array, 2, MinOrder)
+    }
+}
+impl<T: Clone + Debug + PartialEq + PartialOrd> BinaryHeap<T, MaxOrder> {
+    #[allow(unused)]
+    pub fn new_max() -> Self {
+        Self::with_order(MaxOrder)
+    }
+}
+impl<T: Clone + Debug + PartialEq + PartialOrd, O: HeapOrder> BinaryHeap<T, O> {
+    #[allow(unused)]
+    pub fn with_order(order: O) -> Self {
+        Self::with_arity_and_order(2, order)
+    }
+    #[allow(unused)]
+    pub fn with_arity_and_order(d: usize, _order: O) -> Self {
+        Self::heapify(vec![], d)
+    }
+    /// Builds a d-ary heap over `array` in O(n) by trickling down every
+    /// internal node, last one first. Most nodes are near the leaves and
+    /// move at most a few levels, which is what makes this cheaper than `n`
+    /// calls to `add`.
+    #[allow(unused)]
+    pub fn from_vec_with(array: Vec<T>, d: usize, _order: O) -> Self {
+        Self::heapify(array, d)
     }
     pub fn add(&mut self, x: T) -> bool {
         if self.n + 1 > self.array.len() {
@@ -23,6 +63,9 @@ impl<T: Clone + Debug + PartialEq + PartialOrd> BinaryHeap<T> {
         true
     }
     pub fn remove(&mut self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
         let removed = self.array[0].take();
         self.n -= 1;
         self.array.swap(0, self.n);
@@ -32,54 +75,89 @@ impl<T: Clone + Debug + PartialEq + PartialOrd> BinaryHeap<T> {
         }
         removed
     }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.array[..self.n].iter())
+    }
+    #[allow(unused)]
+    pub fn arity(&self) -> usize {
+        self.d
+    }
+    /// The elements in the order `pop` would return them, found in place
+    /// by swapping the root to the end of the live part until one element
+    /// is left, in O(n log n).
+    #[allow(unused)]
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        while self.n > 1 {
+            self.n -= 1;
+            self.array.swap(0, self.n);
+            self.trickle_down(0);
+        }
+        let mut array = self
+            .array
+            .into_vec()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        array.reverse();
+        array
+    }
+    fn heapify(array: Vec<T>, d: usize) -> Self {
+        if d < 2 {
+            panic!("a heap needs at least 2 children per node, got {}", d)
+        }
+        let n = array.len();
+        let mut heap = BinaryHeap {
+            array: array.into_iter().map(Some).collect(),
+            n,
+            d,
+            _order: PhantomData,
+        };
+        let last_parent = if n > 1 { heap.parent_index(n - 1) } else { 0 };
+        for i in (0..=last_parent).rev() {
+            heap.trickle_down(i);
+        }
+        heap
+    }
+    /// Whether the element at `i` belongs above the one at `j`.
+    fn before(&self, i: usize, j: usize) -> bool {
+        O::before(&self.array[i], &self.array[j])
+    }
     fn trickle_down(&mut self, mut i: usize) {
         loop {
-            let mut j = -1;
-            let r = Self::right_index(i);
-            if r < self.n && self.array[r] < self.array[i] {
-                let l = Self::left_index(i);
-                if self.array[l] < self.array[r] {
-                    j = l as isize
-                } else {
-                    j = r as isize
-                }
-            } else {
-                let l = Self::left_index(i);
-                if l < self.n && self.array[l] < self.array[r] {
-                    j = l as isize
-                }
-            }
-            if j >= 0 {
-                self.array.swap(i, j as usize);
-                i = j as usize
-            } else {
+            let mut children = self.children(i);
+            let Some(first) = children.next() else {
+                return;
+            };
+            let j = children.fold(first, |j, k| if self.before(k, j) { k } else { j });
+            if !self.before(j, i) {
                 return;
             }
+            self.array.swap(i, j);
+            i = j
         }
     }
     fn bubble_up(&mut self, mut i: usize) {
         if i == 0 {
             return;
         }
-        let mut parent_index = Self::parent_index(i);
-        while i > 0 && self.array[i] < self.array[parent_index] {
-            println!("parent = {:?}", self.array[parent_index]);
+        let mut parent_index = self.parent_index(i);
+        while i > 0 && self.before(i, parent_index) {
             self.array.swap(i, parent_index);
             i = parent_index;
             if i == 0 {
                 return;
             }
-            parent_index = Self::parent_index(i);
+            parent_index = self.parent_index(i);
         }
     }
-    fn left_index(i: usize) -> usize {
-        i * 2 + 1
-    }
-    fn right_index(i: usize) -> usize {
-        i * 2 + 2
+    /// The indexes of the children of `i` that are in the heap.
+    fn children(&self, i: usize) -> Range<usize> {
+        let first = (self.d * i + 1).min(self.n);
+        first..(self.d * i + self.d + 1).min(self.n)
     }
-    fn parent_index(i: usize) -> usize {
-        (i - 1) / 2
+    fn parent_index(&self, i: usize) -> usize {
+        (i - 1) / self.d
     }
     fn resize(&mut self) {
         let new_array = vec![None; (self.n * 2).max(1)];
@@ -90,9 +168,94 @@ impl<T: Clone + Debug + PartialEq + PartialOrd> BinaryHeap<T> {
         self.array = new_array;
     }
 }
+
+impl<T: Clone + Debug + PartialEq + PartialOrd, O: HeapOrder> PriorityQueue<T>
+    for BinaryHeap<T, O>
+{
+    fn push(&mut self, x: T) {
+        self.add(x);
+    }
+    fn pop(&mut self) -> Option<T> {
+        self.remove()
+    }
+    fn peek(&self) -> Option<T> {
+        self.iter().next().cloned()
+    }
+    fn len(&self) -> usize {
+        self.n
+    }
+    /// Rebuilds the heap over both arrays, in O(n + m).
+    fn merge(&mut self, other: Self) {
+        let mut array = self.array[..self.n]
+            .iter_mut()
+            .filter_map(Option::take)
+            .collect::<Vec<_>>();
+        array.extend(other);
+        *self = Self::heapify(array, self.d);
+    }
+}
+
+/// Iterates over the heap in array (level) order, not in priority order.
+pub struct Iter<'a, T: Clone + Debug + PartialEq + PartialOrd>(std::slice::Iter<'a, Option<T>>);
+impl<'a, T: Clone + Debug + PartialEq + PartialOrd> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()?.as_ref()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug + PartialEq + PartialOrd>(std::vec::IntoIter<Option<T>>);
+impl<T: Clone + Debug + PartialEq + PartialOrd> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()?
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + PartialOrd, O: HeapOrder> IntoIterator for BinaryHeap<T, O> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        let mut array = self.array.into_vec();
+        array.truncate(self.n);
+        IntoIter(array.into_iter())
+    }
+}
+impl<'a, T: Clone + Debug + PartialEq + PartialOrd, O: HeapOrder> IntoIterator
+    for &'a BinaryHeap<T, O>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + PartialEq + PartialOrd, O: HeapOrder + Default> FromIterator<T>
+    for BinaryHeap<T, O>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = BinaryHeap::with_order(O::default());
+        heap.extend(iter);
+        heap
+    }
+}
+impl<T: Clone + Debug + PartialEq + PartialOrd, O: HeapOrder> Extend<T> for BinaryHeap<T, O> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
 #[cfg(test)]
 mod binary_heap_test {
     use super::*;
+    use rand::{thread_rng, Rng};
     #[test]
     fn remove_test() {
         let mut test_node = BinaryHeap {
@@ -114,6 +277,8 @@ mod binary_heap_test {
                 None,
             ]),
             n: 13,
+            d: 2,
+            _order: PhantomData::<MinOrder>,
         };
         assert_eq!(test_node.remove(), Some(4));
         let mut tobe = BinaryHeap {
@@ -135,6 +300,8 @@ mod binary_heap_test {
                 None,
             ]),
             n: 12,
+            d: 2,
+            _order: PhantomData::<MinOrder>,
         };
         assert_eq!(test_node, tobe);
     }
@@ -159,6 +326,8 @@ mod binary_heap_test {
                 None,
             ]),
             n: 12,
+            d: 2,
+            _order: PhantomData::<MinOrder>,
         };
         test_node.add(6);
         let tobe = BinaryHeap {
@@ -180,7 +349,106 @@ mod binary_heap_test {
                 None,
             ]),
             n: 13,
+            d: 2,
+            _order: PhantomData::<MinOrder>,
         };
         assert_eq!(test_node, tobe);
     }
+    #[test]
+    fn trickle_down_test() {
+        // the root has only a left child, which is smaller
+        let mut heap = BinaryHeap {
+            array: Box::new([Some(5), Some(3), None]),
+            n: 2,
+            d: 2,
+            _order: PhantomData::<MinOrder>,
+        };
+        heap.trickle_down(0);
+        assert_eq!(heap.iter().collect::<Vec<_>>(), vec![&3, &5]);
+        let mut heap = vec![3, 1, 2].into_iter().collect::<BinaryHeap<_>>();
+        assert_eq!(heap.remove(), Some(1));
+        assert_eq!(heap.remove(), Some(2));
+        assert_eq!(heap.remove(), Some(3));
+    }
+    #[test]
+    fn heapify_test() {
+        let heap = BinaryHeap::from_vec(vec![9, 4, 7, 1, 8, 2, 2, 6]);
+        assert_eq!(heap.iter().next(), Some(&1));
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 2, 4, 6, 7, 8, 9]);
+        assert_eq!(
+            BinaryHeap::<i32>::from_vec(vec![]).into_sorted_vec(),
+            vec![]
+        );
+        let heap = BinaryHeap::from_vec_with(vec![9, 4, 7, 1, 8], 3, MaxOrder);
+        assert_eq!(heap.into_sorted_vec(), vec![9, 8, 7, 4, 1]);
+    }
+    #[test]
+    fn priority_queue_test() {
+        fn drain<Q: PriorityQueue<i32>>(mut q: Q) -> Vec<i32> {
+            std::iter::from_fn(|| q.pop()).collect()
+        }
+        let mut heap = BinaryHeap::new();
+        assert_eq!(heap.pop(), None);
+        heap.push(5);
+        heap.push(2);
+        heap.merge(vec![7, 1, 4].into_iter().collect());
+        assert_eq!(heap.peek(), Some(1));
+        assert_eq!(heap.len(), 5);
+        assert_eq!(drain(heap), vec![1, 2, 4, 5, 7]);
+        let mut heap = BinaryHeap::new_max();
+        heap.extend(vec![5, 2, 8]);
+        let mut other = BinaryHeap::with_order(MaxOrder);
+        other.extend(vec![3, 9]);
+        heap.merge(other);
+        assert_eq!(heap.peek(), Some(9));
+        assert_eq!(drain(heap), vec![9, 8, 5, 3, 2]);
+    }
+    #[test]
+    fn children_test() {
+        let heap = BinaryHeap::from_vec_with((0..10).collect(), 4, MinOrder);
+        assert_eq!(heap.children(0), 1..5);
+        assert_eq!(heap.children(1), 5..9);
+        assert_eq!(heap.children(2), 9..10);
+        assert_eq!(heap.children(3), 10..10);
+        assert_eq!(heap.parent_index(9), 2);
+        assert_eq!(heap.parent_index(4), 0);
+    }
+    #[test]
+    fn arity_test() {
+        let mut rng = thread_rng();
+        for d in [2, 3, 4, 8] {
+            let v = (0..1000).map(|_| rng.gen_range(0..500)).collect::<Vec<_>>();
+            let mut sorted = v.clone();
+            sorted.sort();
+            let mut heap = BinaryHeap::with_arity(d);
+            heap.extend(v.iter().copied());
+            assert_eq!(heap.arity(), d);
+            assert_eq!(heap.into_sorted_vec(), sorted);
+            let mut heap = BinaryHeap::from_vec_with(v.clone(), d, MaxOrder);
+            for i in 1..heap.n {
+                assert!(heap.array[heap.parent_index(i)] >= heap.array[i]);
+            }
+            heap.extend(vec![250, 600]);
+            assert_eq!(heap.remove(), Some(600));
+            let mut popped = std::iter::from_fn(|| heap.remove()).collect::<Vec<_>>();
+            popped.reverse();
+            let mut tobe = v;
+            tobe.push(250);
+            tobe.sort();
+            assert_eq!(popped, tobe);
+        }
+    }
+    #[test]
+    #[should_panic]
+    fn unary_test() {
+        BinaryHeap::<i32>::with_arity(1);
+    }
+    #[test]
+    fn iter_test() {
+        let mut heap = vec![5, 3].into_iter().collect::<BinaryHeap<_>>();
+        heap.extend(vec![4, 1]);
+        assert_eq!(heap.iter().collect::<Vec<_>>(), vec![&1, &3, &4, &5]);
+        assert_eq!((&heap).into_iter().count(), 4);
+        assert_eq!(heap.into_iter().collect::<Vec<_>>(), vec![1, 3, 4, 5]);
+    }
 }
@@ -0,0 +1,327 @@
+use std::fmt::Debug;
+
+/// Names an element of an `IndexedBinaryHeap` for as long as it stays in the
+/// heap. Slots are reused once their element is removed, but each reuse
+/// bumps the slot's generation, so a stale handle is simply not found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle {
+    slot: usize,
+    generation: usize,
+}
+
+/// A binary min-heap whose elements can be found again by the `Handle`
+/// returned from `add`, so their keys can be changed or they can be removed
+/// in O(log n). The heap array holds slots; `positions` maps each slot back
+/// to its index in the array and is kept in step on every swap. Freed slots
+/// go on `free`, so memory follows the number of live elements.
+#[derive(Debug)]
+pub struct IndexedBinaryHeap<T: Clone + Debug + PartialEq + PartialOrd> {
+    array: Vec<usize>,
+    values: Vec<Option<T>>,
+    positions: Vec<Option<usize>>,
+    generations: Vec<usize>,
+    free: Vec<usize>,
+}
+
+impl<T: Clone + Debug + PartialEq + PartialOrd> IndexedBinaryHeap<T> {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self {
+            array: Vec::new(),
+            values: Vec::new(),
+            positions: Vec::new(),
+            generations: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.array.len()
+    }
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+    #[allow(unused)]
+    pub fn add(&mut self, x: T) -> Handle {
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.values[slot] = Some(x);
+                self.positions[slot] = Some(self.array.len());
+                slot
+            }
+            None => {
+                self.values.push(Some(x));
+                self.positions.push(Some(self.array.len()));
+                self.generations.push(0);
+                self.values.len() - 1
+            }
+        };
+        self.array.push(slot);
+        self.bubble_up(self.array.len() - 1);
+        self.handle(slot)
+    }
+    /// The minimum and its handle.
+    #[allow(unused)]
+    pub fn peek(&self) -> Option<(Handle, &T)> {
+        let &slot = self.array.first()?;
+        Some((self.handle(slot), self.value(slot)))
+    }
+    #[allow(unused)]
+    pub fn get(&self, h: Handle) -> Option<&T> {
+        self.values[self.slot(h)?].as_ref()
+    }
+    #[allow(unused)]
+    pub fn contains(&self, h: Handle) -> bool {
+        self.get(h).is_some()
+    }
+    /// Removes the minimum.
+    #[allow(unused)]
+    pub fn remove_min(&mut self) -> Option<(Handle, T)> {
+        let h = self.handle(*self.array.first()?);
+        let x = self.remove(h)?;
+        Some((h, x))
+    }
+    /// Removes the element named by `h`, wherever it is in the heap.
+    #[allow(unused)]
+    pub fn remove(&mut self, h: Handle) -> Option<T> {
+        let slot = self.slot(h)?;
+        let i = self.positions[slot]?;
+        let last = self.array.len() - 1;
+        self.swap(i, last);
+        self.array.pop();
+        self.positions[slot] = None;
+        self.generations[slot] += 1;
+        self.free.push(slot);
+        let x = self.values[slot].take();
+        if i < self.array.len() {
+            // the moved element may belong above or below `i`
+            self.bubble_up(i);
+            self.trickle_down(i);
+        }
+        x
+    }
+    /// Lowers the key of `h` to `x`. Panics if `h` is not in the heap or `x`
+    /// is larger than its key.
+    #[allow(unused)]
+    pub fn decrease_key(&mut self, h: Handle, x: T) {
+        let (slot, i) = self.position(h);
+        if x > *self.value(slot) {
+            panic!("new key is larger than the current key")
+        }
+        self.values[slot] = Some(x);
+        self.bubble_up(i);
+    }
+    /// Raises the key of `h` to `x`. Panics if `h` is not in the heap or `x`
+    /// is smaller than its key.
+    #[allow(unused)]
+    pub fn increase_key(&mut self, h: Handle, x: T) {
+        let (slot, i) = self.position(h);
+        if x < *self.value(slot) {
+            panic!("new key is smaller than the current key")
+        }
+        self.values[slot] = Some(x);
+        self.trickle_down(i);
+    }
+    fn handle(&self, slot: usize) -> Handle {
+        Handle {
+            slot,
+            generation: self.generations[slot],
+        }
+    }
+    /// The slot `h` names, if its element is still in the heap.
+    fn slot(&self, h: Handle) -> Option<usize> {
+        match self.generations.get(h.slot) {
+            Some(&generation) if generation == h.generation => Some(h.slot),
+            _ => None,
+        }
+    }
+    fn position(&self, h: Handle) -> (usize, usize) {
+        match self.slot(h) {
+            Some(slot) => (slot, self.positions[slot].unwrap()),
+            None => panic!("handle {:?} is not in the heap", h),
+        }
+    }
+    fn value(&self, slot: usize) -> &T {
+        self.values[slot].as_ref().unwrap()
+    }
+    fn less(&self, i: usize, j: usize) -> bool {
+        self.value(self.array[i]) < self.value(self.array[j])
+    }
+    fn swap(&mut self, i: usize, j: usize) {
+        self.array.swap(i, j);
+        self.positions[self.array[i]] = Some(i);
+        self.positions[self.array[j]] = Some(j);
+    }
+    fn trickle_down(&mut self, mut i: usize) {
+        loop {
+            let l = Self::left_index(i);
+            let r = Self::right_index(i);
+            let mut j = i;
+            if l < self.array.len() && self.less(l, j) {
+                j = l
+            }
+            if r < self.array.len() && self.less(r, j) {
+                j = r
+            }
+            if j == i {
+                return;
+            }
+            self.swap(i, j);
+            i = j
+        }
+    }
+    fn bubble_up(&mut self, mut i: usize) {
+        while i > 0 && self.less(i, Self::parent_index(i)) {
+            self.swap(i, Self::parent_index(i));
+            i = Self::parent_index(i);
+        }
+    }
+    fn left_index(i: usize) -> usize {
+        i * 2 + 1
+    }
+    fn right_index(i: usize) -> usize {
+        i * 2 + 2
+    }
+    fn parent_index(i: usize) -> usize {
+        (i - 1) / 2
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + PartialOrd> Default for IndexedBinaryHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod indexed_binary_heap_test {
+    use super::*;
+    use crate::{interfaces::graph::Graph, structs::graphs::adjacency_list::AdjacencyList};
+    use rand::{thread_rng, Rng};
+    #[test]
+    fn test() {
+        let mut heap = IndexedBinaryHeap::new();
+        let a = heap.add(5);
+        let b = heap.add(3);
+        let c = heap.add(8);
+        assert_eq!(heap.peek(), Some((b, &3)));
+        heap.decrease_key(c, 1);
+        assert_eq!(heap.peek(), Some((c, &1)));
+        heap.increase_key(c, 9);
+        assert_eq!(heap.peek(), Some((b, &3)));
+        assert_eq!(heap.remove(b), Some(3));
+        assert_eq!(heap.remove(b), None);
+        assert!(!heap.contains(b));
+        assert_eq!(heap.get(c), Some(&9));
+        assert_eq!(heap.remove_min(), Some((a, 5)));
+        assert_eq!(heap.remove_min(), Some((c, 9)));
+        assert_eq!(heap.remove_min(), None);
+        assert!(heap.is_empty());
+    }
+    #[test]
+    fn reuse_test() {
+        let mut heap = IndexedBinaryHeap::new();
+        let a = heap.add(5);
+        assert_eq!(heap.remove(a), Some(5));
+        let b = heap.add(7);
+        assert_ne!(a, b);
+        assert_eq!(heap.values.len(), 1);
+        assert!(!heap.contains(a));
+        assert_eq!(heap.get(a), None);
+        assert_eq!(heap.remove(a), None);
+        assert_eq!(heap.get(b), Some(&7));
+    }
+    #[test]
+    #[should_panic]
+    fn stale_handle_test() {
+        let mut heap = IndexedBinaryHeap::new();
+        let a = heap.add(5);
+        heap.remove(a);
+        heap.add(7);
+        heap.decrease_key(a, 1);
+    }
+    #[test]
+    #[should_panic]
+    fn decrease_key_test() {
+        let mut heap = IndexedBinaryHeap::new();
+        let h = heap.add(5);
+        heap.decrease_key(h, 6);
+    }
+    #[test]
+    fn random_test() {
+        let mut rng = thread_rng();
+        let mut heap = IndexedBinaryHeap::new();
+        let mut tobe: Vec<(Handle, i32)> = Vec::new();
+        for _ in 0..10000 {
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let x = rng.gen_range(0..1000);
+                    tobe.push((heap.add(x), x));
+                }
+                2 if !tobe.is_empty() => {
+                    let i = rng.gen_range(0..tobe.len());
+                    let (h, x) = tobe[i];
+                    let y = rng.gen_range(0..1000);
+                    if y < x {
+                        heap.decrease_key(h, y);
+                    } else {
+                        heap.increase_key(h, y);
+                    }
+                    tobe[i].1 = y;
+                }
+                _ if !tobe.is_empty() => {
+                    let i = rng.gen_range(0..tobe.len());
+                    let (h, x) = tobe.swap_remove(i);
+                    assert_eq!(heap.remove(h), Some(x));
+                }
+                _ => {}
+            }
+            assert_eq!(heap.len(), tobe.len());
+            assert_eq!(
+                heap.peek().map(|(_, &x)| x),
+                tobe.iter().map(|&(_, x)| x).min()
+            );
+            for (i, &slot) in heap.array.iter().enumerate() {
+                assert_eq!(heap.positions[slot], Some(i));
+            }
+            assert_eq!(heap.values.len(), heap.len() + heap.free.len());
+        }
+    }
+    #[test]
+    fn dijkstra_test() {
+        let n = 6;
+        let mut graph = AdjacencyList::new(n);
+        let weights = [
+            (0, 1, 7),
+            (0, 2, 9),
+            (0, 5, 14),
+            (1, 2, 10),
+            (1, 3, 15),
+            (2, 3, 11),
+            (2, 5, 2),
+            (3, 4, 6),
+            (5, 4, 9),
+        ];
+        for &(i, j, _) in weights.iter() {
+            graph.add_edge(i, j);
+        }
+        let weight = |i, j| weights.iter().find(|w| w.0 == i && w.1 == j).unwrap().2;
+        let mut dist = vec![usize::MAX; n];
+        let mut heap = IndexedBinaryHeap::new();
+        let handles = (0..n)
+            .map(|i| heap.add((if i == 0 { 0 } else { usize::MAX }, i)))
+            .collect::<Vec<_>>();
+        while let Some((_, (d, i))) = heap.remove_min() {
+            dist[i] = d;
+            for j in graph.out_edges(i) {
+                if let Some(&(dj, _)) = heap.get(handles[j]) {
+                    if d + weight(i, j) < dj {
+                        heap.decrease_key(handles[j], (d + weight(i, j), j));
+                    }
+                }
+            }
+        }
+        assert_eq!(dist, vec![0, 7, 9, 20, 20, 11]);
+    }
+}
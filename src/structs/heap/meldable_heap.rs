@@ -1,41 +1,107 @@
 use std::{
     cell::RefCell,
     fmt::Debug,
+    marker::PhantomData,
     ops::Deref,
     rc::{Rc, Weak},
 };
 
-use crate::structs::binary_tree::random_binary_search_tree::RandomGenerator;
+use rand::{rngs::StdRng, SeedableRng};
 
+use crate::interfaces::{
+    priority_queue::{HeapOrder, MinOrder, PriorityQueue},
+    random_generator::RandomGenerator,
+};
+
+/// A randomized meldable heap: a binary tree in heap order whose only
+/// operation is merging two heaps along random paths. `O` decides whether
+/// the smallest or the largest element is at the root.
 #[derive(Debug)]
-pub struct MeldableHeap<T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator> {
+pub struct MeldableHeap<
+    T: Clone + PartialEq + PartialOrd + Debug,
+    R: RandomGenerator = StdRng,
+    O: HeapOrder = MinOrder,
+> {
     root: WrapNode<T>,
     rand_gen: R,
+    n: usize,
+    _order: PhantomData<O>,
+}
+impl<T: Clone + PartialEq + PartialOrd + Debug> MeldableHeap<T> {
+    /// Builds a heap whose merge choices, and so whose shape, are fixed by
+    /// `seed`.
+    #[allow(unused)]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::new(StdRng::seed_from_u64(seed))
+    }
 }
 impl<T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator> MeldableHeap<T, R> {
     pub fn new(rand_gen: R) -> Self {
+        Self::with_order(rand_gen, MinOrder)
+    }
+}
+impl<T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator, O: HeapOrder>
+    MeldableHeap<T, R, O>
+{
+    #[allow(unused)]
+    pub fn with_order(rand_gen: R, _order: O) -> Self {
         Self {
             root: WrapNode(None),
             rand_gen,
+            n: 0,
+            _order: PhantomData,
         }
     }
     pub fn add(&mut self, x: T) -> bool {
         let mut node = WrapNode::new(x);
-        let root = node.merge(self.root.clone(), &mut self.rand_gen);
+        let root = node.merge::<O>(self.root.clone(), &mut self.rand_gen);
         self.root = root;
         self.root.set_parent(WrapNode(None));
+        self.n += 1;
         true
     }
+    /// Clones every element on the way out; see `Iter`.
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            stack: self.root.iter().cloned().collect(),
+            _heap: PhantomData,
+        }
+    }
     pub fn remove(&mut self) -> Option<T> {
-        let x = self.root.value();
+        let x = self.root.value()?;
         self.root = self
             .root
             .left()
-            .merge(self.root.right(), &mut self.rand_gen);
-        if self.root.is_none() {
+            .merge::<O>(self.root.right(), &mut self.rand_gen);
+        if self.root.is_some() {
             self.root.set_parent(WrapNode(None))
         }
-        x
+        self.n -= 1;
+        Some(x)
+    }
+}
+
+impl<T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator, O: HeapOrder> PriorityQueue<T>
+    for MeldableHeap<T, R, O>
+{
+    fn push(&mut self, x: T) {
+        self.add(x);
+    }
+    fn pop(&mut self) -> Option<T> {
+        self.remove()
+    }
+    fn peek(&self) -> Option<T> {
+        self.root.value()
+    }
+    fn len(&self) -> usize {
+        self.n
+    }
+    /// Melds the two trees in O(log n) expected time.
+    fn merge(&mut self, other: Self) {
+        self.root = self.root.merge::<O>(other.root, &mut self.rand_gen);
+        self.root.set_parent(WrapNode(None));
+        self.n += other.n;
     }
 }
 
@@ -53,24 +119,24 @@ impl<T: Clone + PartialEq + PartialOrd + Debug> WrapNode<T> {
     fn new(x: T) -> Self {
         WrapNode(Some(Rc::new(RefCell::new(MeldableHeapNode::new(x)))))
     }
-    fn merge(&mut self, mut other: Self, rand: &mut impl RandomGenerator) -> Self {
+    fn merge<O: HeapOrder>(&mut self, mut other: Self, rand: &mut impl RandomGenerator) -> Self {
         if self.is_none() {
             return other;
         }
         if other.is_none() {
             return self.clone();
         }
-        if self.value() > other.value() {
-            return other.merge(self.clone(), rand);
+        if O::before(&other.value(), &self.value()) {
+            return other.merge::<O>(self.clone(), rand);
         }
         if rand.gen_rand() % 2 == 0 {
-            let left = self.left().merge(other, rand);
+            let left = self.left().merge::<O>(other, rand);
             self.set_left(left);
             if self.left().is_some() {
                 self.left().set_parent(self.clone());
             }
         } else {
-            let right = self.right().merge(other, rand);
+            let right = self.right().merge::<O>(other, rand);
             self.set_right(right);
             if self.right().is_some() {
                 self.right().set_parent(self.clone());
@@ -157,6 +223,76 @@ impl<T: Clone + PartialEq + PartialOrd + Debug> MeldableHeapNode<T> {
     }
 }
 
+/// Iterates over the heap in preorder, not in priority order.
+///
+/// The nodes sit behind `RefCell`, so a `&T` cannot outlive the borrow and
+/// each item is a clone of `T`. The lifetime only keeps the heap from being
+/// changed while the iterator holds its nodes.
+pub struct Iter<'a, T: Clone + PartialEq + PartialOrd + Debug> {
+    stack: Vec<Rc<RefCell<MeldableHeapNode<T>>>>,
+    _heap: PhantomData<&'a T>,
+}
+impl<'a, T: Clone + PartialEq + PartialOrd + Debug> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let node = node.borrow();
+        self.stack.extend(node.right.iter().cloned());
+        self.stack.extend(node.left.iter().cloned());
+        Some(node.value.clone())
+    }
+}
+
+/// Removes the elements one by one, so they come out in priority order.
+pub struct IntoIter<T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator, O: HeapOrder>(
+    MeldableHeap<T, R, O>,
+);
+impl<T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator, O: HeapOrder> Iterator
+    for IntoIter<T, R, O>
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.remove()
+    }
+}
+
+impl<T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator, O: HeapOrder> IntoIterator
+    for MeldableHeap<T, R, O>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, R, O>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+impl<'a, T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator, O: HeapOrder> IntoIterator
+    for &'a MeldableHeap<T, R, O>
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + PartialEq + PartialOrd + Debug, O: HeapOrder + Default> FromIterator<T>
+    for MeldableHeap<T, StdRng, O>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = MeldableHeap::with_order(StdRng::from_entropy(), O::default());
+        heap.extend(iter);
+        heap
+    }
+}
+impl<T: Clone + PartialEq + PartialOrd + Debug, R: RandomGenerator, O: HeapOrder> Extend<T>
+    for MeldableHeap<T, R, O>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod meldable_heap_test {
     struct RandomGeneratorMock(Vec<usize>);
@@ -198,4 +334,67 @@ mod meldable_heap_test {
             assert_eq!(tree.remove(), Some(i));
         }
     }
+    #[test]
+    fn seed_test() {
+        let mut heap = MeldableHeap::with_seed(7);
+        let mut other = MeldableHeap::with_seed(7);
+        heap.extend((0..100).rev());
+        other.extend((0..100).rev());
+        assert_eq!(
+            heap.iter().collect::<Vec<_>>(),
+            other.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(heap.remove(), other.remove());
+        assert_eq!(
+            heap.iter().collect::<Vec<_>>(),
+            other.iter().collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn remove_parent_test() {
+        let mut heap = MeldableHeap::with_seed(3);
+        heap.extend(0..10);
+        for i in 0..9 {
+            assert_eq!(heap.remove(), Some(i));
+            // the new root must not keep a link to the removed one
+            assert!(heap.root.as_ref().unwrap().borrow().parent.is_none());
+        }
+    }
+    #[test]
+    fn priority_queue_test() {
+        use crate::interfaces::priority_queue::MaxOrder;
+        fn drain<Q: PriorityQueue<i32>>(mut q: Q) -> Vec<i32> {
+            std::iter::from_fn(|| q.pop()).collect()
+        }
+        let mut heap = MeldableHeap::with_seed(1);
+        assert_eq!(heap.pop(), None);
+        heap.extend(vec![5, 2]);
+        let mut other = MeldableHeap::with_seed(2);
+        other.extend(vec![7, 1, 4]);
+        heap.merge(other);
+        assert_eq!(heap.peek(), Some(1));
+        assert_eq!(heap.len(), 5);
+        assert_eq!(drain(heap), vec![1, 2, 4, 5, 7]);
+        let mut heap = MeldableHeap::with_order(StdRng::seed_from_u64(1), MaxOrder);
+        heap.extend(vec![5, 2, 8]);
+        let mut other = MeldableHeap::with_order(StdRng::seed_from_u64(2), MaxOrder);
+        other.extend(vec![3, 9]);
+        heap.merge(other);
+        assert_eq!(heap.peek(), Some(9));
+        assert_eq!(heap.len(), 5);
+        assert_eq!(drain(heap), vec![9, 8, 5, 3, 2]);
+    }
+    #[test]
+    fn iter_test() {
+        use crate::interfaces::priority_queue::MaxOrder;
+        let mut heap = vec![5, 3].into_iter().collect::<MeldableHeap<_>>();
+        heap.extend(vec![4, 1]);
+        let mut values = heap.iter().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![1, 3, 4, 5]);
+        assert_eq!((&heap).into_iter().count(), 4);
+        assert_eq!(heap.into_iter().collect::<Vec<_>>(), vec![1, 3, 4, 5]);
+        let heap = (0..5).collect::<MeldableHeap<_, StdRng, MaxOrder>>();
+        assert_eq!(heap.into_iter().collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+    }
 }
@@ -1,12 +1,12 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
 
 use crate::{
-    interfaces::list::List,
+    interfaces::{deque::Deque, list::List},
     types::link::{StrongLink, WeakLink},
 };
 #[derive(Debug)]
 pub struct DLList<T: Default + Clone + Debug + Eq + PartialEq> {
-    n: isize,
+    n: usize,
     head: NodeWrapper<T>,
     tail: NodeWrapper<T>,
 }
@@ -18,26 +18,175 @@ impl<T: Clone + Debug + Eq + PartialEq + Default> DLList<T> {
         let tail = NodeWrapper::new(Default::default());
         head.change_next(&tail);
         tail.change_prev(&head);
-        DLList { n: -1, head, tail }
+        DLList { n: 0, head, tail }
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.head.next(),
+            back: self.tail.prev(),
+            len: self.n,
+            _list: PhantomData,
+        }
+    }
+    #[allow(unused)]
+    pub fn cursor_front(&mut self) -> CursorMut<'_, T> {
+        let current = self.get_node(0);
+        CursorMut {
+            list: self,
+            current,
+            index: 0,
+        }
+    }
+    #[allow(unused)]
+    pub fn cursor_back(&mut self) -> CursorMut<'_, T> {
+        let index = self.n.saturating_sub(1);
+        let current = self.get_node(index);
+        CursorMut {
+            list: self,
+            current,
+            index,
+        }
     }
     fn get_node(&self, index: usize) -> Option<NodeWrapper<T>> {
-        if index as isize > self.n {
+        if index >= self.n {
             return None;
         }
-        let mut node = self.head.next();
-        for _ in 0..index {
-            if node.is_none() {
-                return None;
+        if index < self.n / 2 {
+            let mut node = self.head.next();
+            for _ in 0..index {
+                node = node?.next()
+            }
+            node
+        } else {
+            let mut node = self.tail.prev();
+            for _ in index..(self.n - 1) {
+                node = node?.prev()
+            }
+            node
+        }
+    }
+}
+
+/// A cursor over a `DLList` that edits the list at its position in O(1).
+///
+/// Like `std::collections::linked_list::CursorMut`, the cursor may rest on a
+/// "ghost" position between the last and the first element, where
+/// `current` is `None` and `index` equals the list size.
+pub struct CursorMut<'a, T: Default + Clone + Debug + Eq + PartialEq> {
+    list: &'a mut DLList<T>,
+    current: Option<NodeWrapper<T>>,
+    index: usize,
+}
+impl<'a, T: Default + Clone + Debug + Eq + PartialEq> CursorMut<'a, T> {
+    #[allow(unused)]
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+    #[allow(unused)]
+    pub fn current(&self) -> Option<T> {
+        self.current.as_ref().map(|node| node.value())
+    }
+    #[allow(unused)]
+    pub fn set_current(&mut self, x: T) -> Option<T> {
+        let node = self.current.as_ref()?;
+        Some(std::mem::replace(&mut node.0.borrow_mut().x, x))
+    }
+    #[allow(unused)]
+    pub fn move_next(&mut self) {
+        let next = match self.current.take() {
+            Some(node) => {
+                self.index += 1;
+                node.next()
+            }
+            None => {
+                self.index = 0;
+                self.list.head.next()
             }
-            node = node.unwrap().next()
+        };
+        self.current = next.filter(|node| !node.is(&self.list.tail));
+    }
+    #[allow(unused)]
+    pub fn move_prev(&mut self) {
+        let prev = match self.current.take() {
+            Some(node) => node.prev(),
+            None => self.list.tail.prev(),
+        };
+        self.current = prev.filter(|node| !node.is(&self.list.head));
+        self.index = match self.current {
+            Some(_) => self.index - 1,
+            None => self.list.n,
+        };
+    }
+    /// Inserts `x` after the current element, or at the front when the
+    /// cursor is on the ghost position.
+    #[allow(unused)]
+    pub fn insert_after(&mut self, x: T) {
+        let node = NodeWrapper::new(x);
+        match self.current.as_ref() {
+            Some(current) => current.change_next(&node),
+            None => self.list.head.change_next(&node),
+        }
+        self.list.n += 1;
+        if self.current.is_none() {
+            self.index = self.list.n;
+        }
+    }
+    /// Inserts `x` before the current element, or at the back when the
+    /// cursor is on the ghost position.
+    #[allow(unused)]
+    pub fn insert_before(&mut self, x: T) {
+        let node = NodeWrapper::new(x);
+        match self.current.as_ref() {
+            Some(current) => current.change_prev(&node),
+            None => self.list.tail.change_prev(&node),
+        }
+        self.list.n += 1;
+        self.index += 1;
+    }
+    /// Removes the current element and moves the cursor to the next one.
+    #[allow(unused)]
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current.take()?;
+        let next = node.next();
+        let x = node.value();
+        node.delete();
+        self.list.n -= 1;
+        self.current = next.filter(|node| !node.is(&self.list.tail));
+        if self.current.is_none() {
+            self.index = self.list.n;
+        }
+        Some(x)
+    }
+    /// Moves every element of `other` after the current element (or to the
+    /// front on the ghost position) without walking either list.
+    #[allow(unused)]
+    pub fn splice(&mut self, other: DLList<T>) {
+        if other.n == 0 {
+            return;
+        }
+        let (first, last) = (other.head.next().unwrap(), other.tail.prev().unwrap());
+        other.head.0.borrow_mut().next = Some(other.tail.0.clone());
+        other.tail.0.borrow_mut().prev = Some(Rc::downgrade(&other.head.0));
+        let prev = self
+            .current
+            .clone()
+            .unwrap_or_else(|| self.list.head.clone());
+        let next = prev.next().unwrap();
+        prev.0.borrow_mut().next = Some(first.0.clone());
+        first.0.borrow_mut().prev = Some(Rc::downgrade(&prev.0));
+        last.0.borrow_mut().next = Some(next.0.clone());
+        next.0.borrow_mut().prev = Some(Rc::downgrade(&last.0));
+        self.list.n += other.n;
+        if self.current.is_none() {
+            self.index = self.list.n;
         }
-        node
     }
 }
 
 impl<T: Default + Clone + Debug + Eq + PartialEq> List<T> for DLList<T> {
     fn size(&self) -> usize {
-        self.n as usize
+        self.n
     }
     fn set(&mut self, index: usize, x: T) -> () {
         if let Some(node) = self.get_node(index) {
@@ -45,20 +194,21 @@ impl<T: Default + Clone + Debug + Eq + PartialEq> List<T> for DLList<T> {
         }
     }
     fn remove(&mut self, index: usize) -> Option<T> {
-        let result = self.get_node(index).map(|node| node.value());
-        self.get_node(index).map(|node| node.delete());
-        self.n = self.n - 1;
-        result
+        let node = self.get_node(index)?;
+        let result = node.value();
+        node.delete();
+        self.n -= 1;
+        Some(result)
     }
     fn get(&self, index: usize) -> Option<T> {
         self.get_node(index).map(|node| node.value())
     }
     fn add(&mut self, index: usize, x: T) -> () {
         let node = NodeWrapper::new(x);
-        if index as isize > (self.n + 1) {
+        if index > self.n {
             panic!("do not adding index : {} ", index)
         }
-        if index as isize > self.n {
+        if index == self.n {
             self.tail.change_prev(&node);
             self.n += 1;
             return;
@@ -69,7 +219,111 @@ impl<T: Default + Clone + Debug + Eq + PartialEq> List<T> for DLList<T> {
             return;
         }
         let old_node = self.get_node(index).unwrap();
-        old_node.change_prev(&node)
+        old_node.change_prev(&node);
+        self.n += 1;
+    }
+}
+
+impl<T: Default + Clone + Debug + Eq + PartialEq> Deque<T> for DLList<T> {
+    fn push_front(&mut self, x: T) {
+        self.add(0, x)
+    }
+    fn push_back(&mut self, x: T) {
+        self.add(self.n, x)
+    }
+    fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+    fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.n.checked_sub(1)?)
+    }
+    fn front(&self) -> Option<T> {
+        self.get(0)
+    }
+    fn back(&self) -> Option<T> {
+        self.get(self.n.checked_sub(1)?)
+    }
+}
+
+pub struct Iter<'a, T: Default + Clone + Debug + Eq + PartialEq> {
+    front: Option<NodeWrapper<T>>,
+    back: Option<NodeWrapper<T>>,
+    len: usize,
+    _list: PhantomData<&'a DLList<T>>,
+}
+impl<'a, T: Default + Clone + Debug + Eq + PartialEq> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.front.take()?;
+        self.front = node.next();
+        self.len -= 1;
+        Some(node.value())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+impl<'a, T: Default + Clone + Debug + Eq + PartialEq> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.back.take()?;
+        self.back = node.prev();
+        self.len -= 1;
+        Some(node.value())
+    }
+}
+impl<'a, T: Default + Clone + Debug + Eq + PartialEq> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IntoIter<T: Default + Clone + Debug + Eq + PartialEq>(DLList<T>);
+impl<T: Default + Clone + Debug + Eq + PartialEq> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.remove(0)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.n, Some(self.0.n))
+    }
+}
+
+impl<T: Default + Clone + Debug + Eq + PartialEq> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let last = self.0.n.checked_sub(1)?;
+        self.0.remove(last)
+    }
+}
+impl<T: Default + Clone + Debug + Eq + PartialEq> ExactSizeIterator for IntoIter<T> {}
+
+impl<T: Default + Clone + Debug + Eq + PartialEq> IntoIterator for DLList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+impl<'a, T: Default + Clone + Debug + Eq + PartialEq> IntoIterator for &'a DLList<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Default + Clone + Debug + Eq + PartialEq> FromIterator<T> for DLList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DLList::new();
+        list.extend(iter);
+        list
+    }
+}
+impl<T: Default + Clone + Debug + Eq + PartialEq> Extend<T> for DLList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
     }
 }
 #[derive(Debug, Clone)]
@@ -81,13 +335,19 @@ impl<T: Default + Clone + Debug + Eq + PartialEq> NodeWrapper<T> {
     fn value(&self) -> T {
         self.0.borrow().x.clone()
     }
+    fn is(&self, other: &NodeWrapper<T>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
     fn delete(self) {
         let prev = self.prev();
         let next = self.next();
         if let (Some(prev), Some(next)) = (prev, next) {
-            next.change_prev(&prev);
+            prev.0.borrow_mut().next = Some(next.0.clone());
+            next.0.borrow_mut().prev = Some(Rc::downgrade(&prev.0));
         }
-        drop(self)
+        let mut node = self.0.borrow_mut();
+        node.prev = None;
+        node.next = None;
     }
     fn next(&self) -> Option<Self> {
         let n = &self.0.borrow().next; //.map(|node| NodeWrapper(node))
@@ -180,6 +440,21 @@ mod dl_list_test {
         assert_eq!(list.get(4), None);
     }
     #[test]
+    fn deque_test() {
+        let mut list = DLList::new();
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.back(), None);
+        list.push_back("world");
+        list.push_front("hello");
+        list.push_back("!!");
+        assert_eq!(list.front(), Some("hello"));
+        assert_eq!(list.back(), Some("!!"));
+        assert_eq!(list.pop_back(), Some("!!"));
+        assert_eq!(list.pop_front(), Some("hello"));
+        assert_eq!(list.pop_back(), Some("world"));
+        assert_eq!(list.pop_back(), None);
+    }
+    #[test]
     fn get_node_test() {
         let mut list = DLList::new();
         list.add(0, "*****");
@@ -202,6 +477,106 @@ mod dl_list_test {
         assert_eq!(list.get(1).unwrap(), "world");
         assert_eq!(list.get(2).unwrap(), "*****");
         assert_eq!(list.get(3), None);
+        assert_eq!(list.size(), 3);
+        assert_eq!(list.remove(3), None);
+        assert_eq!(list.remove(1).unwrap(), "world");
+        assert_eq!(list.get(1).unwrap(), "*****");
+        assert_eq!(list.size(), 2);
+    }
+    #[test]
+    fn size_test() {
+        let mut list = DLList::new();
+        assert_eq!(list.size(), 0);
+        list.add(0, "world");
+        list.add(0, "hello");
+        list.add(1, "big");
+        list.add(3, "!");
+        assert_eq!(list.size(), 4);
+        assert_eq!(list.get(3), Some("!"));
+        assert_eq!(list.get(4), None);
+    }
+    #[test]
+    fn remove_relink_test() {
+        let mut list = DLList::new();
+        list.add(0, "a");
+        list.add(1, "b");
+        list.add(2, "c");
+        assert_eq!(list.remove(1), Some("b"));
+        assert_eq!(list.get_node(1).unwrap().prev().unwrap().value(), "a");
+        assert_eq!(list.get_node(0).unwrap().prev().unwrap().value(), "");
+        // adding before "c" goes through its prev link
+        list.add(1, "d");
+        assert_eq!(list.get(0), Some("a"));
+        assert_eq!(list.get(1), Some("d"));
+        assert_eq!(list.get(2), Some("c"));
+    }
+    #[test]
+    fn iter_test() {
+        let mut list = ["hello", "world"].into_iter().collect::<DLList<_>>();
+        list.extend(["good", "bye"]);
+        list.add(2, "!!");
+        assert_eq!(list.size(), 5);
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            vec!["hello", "world", "!!", "good", "bye"]
+        );
+        assert_eq!((&list).into_iter().count(), 5);
+        assert_eq!(
+            list.iter().rev().collect::<Vec<_>>(),
+            vec!["bye", "good", "!!", "world", "hello"]
+        );
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some("hello"));
+        assert_eq!(iter.next_back(), Some("bye"));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec!["world", "!!", "good"]);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next_back(), Some("bye"));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(
+            iter.collect::<Vec<_>>(),
+            vec!["hello", "world", "!!", "good"]
+        );
+    }
+    #[test]
+    fn cursor_test() {
+        let mut list = (1..=3).collect::<DLList<_>>();
+        let mut cursor = list.cursor_front();
+        assert_eq!(cursor.current(), Some(1));
+        cursor.insert_after(10);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.current(), Some(10));
+        assert_eq!(cursor.remove_current(), Some(10));
+        assert_eq!(cursor.current(), Some(2));
+        cursor.insert_before(20);
+        assert_eq!(cursor.index(), Some(2));
+        cursor.splice((4..=5).collect());
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(4));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(3));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.insert_before(6);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(6));
+        assert_eq!(cursor.set_current(7), Some(6));
+        assert_eq!(list.size(), 7);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 20, 2, 4, 5, 3, 7]);
+        assert_eq!(
+            list.iter().rev().collect::<Vec<_>>(),
+            vec![7, 3, 5, 4, 2, 20, 1]
+        );
+        let mut cursor = list.cursor_back();
+        while cursor.remove_current().is_some() {
+            cursor.move_prev();
+        }
+        assert_eq!(list.size(), 0);
+        assert_eq!(list.iter().next(), None);
     }
     #[test]
     fn node_wrapper_test() {
@@ -1,9 +1,415 @@
-use std::fmt::Debug;
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
-pub struct SEList {}
+use crate::{
+    interfaces::list::List,
+    types::link::{StrongLink, WeakLink},
+};
 
-//struct BDeque<T: Clone + Debug> {
-//n: usize,
-//block_size: usize,
-//}
-//struct Node<T: Clone + Debug> {}
+/// A space-efficient linked list: a `DLList`-style chain of blocks, each
+/// block a `BDeque` of capacity `b + 1` holding between `b - 1` and `b + 1`
+/// elements (only the last block may hold fewer). This keeps the space used
+/// to `n + O(b + n / b)`.
+#[derive(Debug)]
+pub struct SEList<T: Clone + Default + Debug> {
+    n: usize,
+    b: usize,
+    head: StrongLink<Node<T>>,
+    tail: StrongLink<Node<T>>,
+}
+
+impl<T: Clone + Default + Debug> SEList<T> {
+    #[allow(unused)]
+    pub fn new(b: usize) -> Self {
+        if b == 0 {
+            panic!("block size must be positive")
+        }
+        let head = Rc::new(RefCell::new(Node::new(0)));
+        let tail = Rc::new(RefCell::new(Node::new(0)));
+        head.borrow_mut().next = Some(tail.clone());
+        tail.borrow_mut().prev = Some(Rc::downgrade(&head));
+        Self {
+            n: 0,
+            b,
+            head,
+            tail,
+        }
+    }
+    fn get_location(&self, mut i: usize) -> (StrongLink<Node<T>>, usize) {
+        if i < self.n / 2 {
+            let mut u = Node::next(&self.head);
+            while i >= Node::size(&u) {
+                i -= Node::size(&u);
+                u = Node::next(&u);
+            }
+            (u, i)
+        } else {
+            let mut u = self.tail.clone();
+            let mut index = self.n;
+            while i < index {
+                u = Node::prev(&u);
+                index -= Node::size(&u);
+            }
+            (u, i - index)
+        }
+    }
+    fn push_back(&mut self, x: T) {
+        let mut last = Node::prev(&self.tail);
+        if Rc::ptr_eq(&last, &self.head) || Node::size(&last) == self.b + 1 {
+            last = Node::add_before(&self.tail, self.b);
+        }
+        let size = Node::size(&last);
+        last.borrow_mut().d.add(size, x);
+        self.n += 1;
+    }
+    /// Turns `b` full blocks starting at `u` into `b + 1` blocks of size `b`.
+    fn spread(&mut self, u: &StrongLink<Node<T>>) {
+        let mut w = u.clone();
+        for _ in 0..self.b {
+            w = Node::next(&w);
+        }
+        w = Node::add_before(&w, self.b);
+        while !Rc::ptr_eq(&w, u) {
+            while Node::size(&w) < self.b {
+                Node::shift_from_prev(&w);
+            }
+            w = Node::prev(&w);
+        }
+    }
+    /// Turns `b` blocks of size `b - 1` starting at `u` into `b - 1` blocks
+    /// of size `b`.
+    fn gather(&mut self, u: &StrongLink<Node<T>>) {
+        let mut w = u.clone();
+        for _ in 0..(self.b - 1) {
+            while Node::size(&w) < self.b {
+                Node::shift_from_next(&w);
+            }
+            w = Node::next(&w);
+        }
+        Node::unlink(&w);
+    }
+    #[cfg(test)]
+    fn block_sizes(&self) -> Vec<usize> {
+        let mut sizes = Vec::new();
+        let mut u = Node::next(&self.head);
+        while !Rc::ptr_eq(&u, &self.tail) {
+            sizes.push(Node::size(&u));
+            u = Node::next(&u);
+        }
+        sizes
+    }
+    #[cfg(test)]
+    fn block_capacities(&self) -> Vec<usize> {
+        let mut capacities = Vec::new();
+        let mut u = Node::next(&self.head);
+        while !Rc::ptr_eq(&u, &self.tail) {
+            capacities.push(u.borrow().d.array.len());
+            u = Node::next(&u);
+        }
+        capacities
+    }
+}
+
+impl<T: Clone + Default + Debug> List<T> for SEList<T> {
+    fn size(&self) -> usize {
+        self.n
+    }
+    fn get(&self, i: usize) -> Option<T> {
+        if i >= self.n {
+            return None;
+        }
+        let (u, j) = self.get_location(i);
+        let x = u.borrow().d.get(j);
+        x
+    }
+    fn set(&mut self, i: usize, x: T) {
+        if i >= self.n {
+            return;
+        }
+        let (u, j) = self.get_location(i);
+        u.borrow_mut().d.set(j, x);
+    }
+    fn add(&mut self, i: usize, x: T) {
+        if i > self.n {
+            panic!("do not adding index : {} ", i)
+        }
+        if i == self.n {
+            self.push_back(x);
+            return;
+        }
+        let (location, j) = self.get_location(i);
+        let mut u = location.clone();
+        let mut r = 0;
+        while r < self.b && !Rc::ptr_eq(&u, &self.tail) && Node::size(&u) == self.b + 1 {
+            u = Node::next(&u);
+            r += 1;
+        }
+        if r == self.b {
+            self.spread(&location);
+            u = location.clone();
+        }
+        if Rc::ptr_eq(&u, &self.tail) {
+            u = Node::add_before(&u, self.b);
+        }
+        while !Rc::ptr_eq(&u, &location) {
+            Node::shift_from_prev(&u);
+            u = Node::prev(&u);
+        }
+        u.borrow_mut().d.add(j, x);
+        self.n += 1;
+    }
+    fn remove(&mut self, i: usize) -> Option<T> {
+        if i >= self.n {
+            return None;
+        }
+        let (location, j) = self.get_location(i);
+        let mut u = location.clone();
+        let mut r = 0;
+        while r < self.b && !Rc::ptr_eq(&u, &self.tail) && Node::size(&u) == self.b - 1 {
+            u = Node::next(&u);
+            r += 1;
+        }
+        if r == self.b {
+            self.gather(&location);
+        }
+        u = location;
+        let x = u.borrow_mut().d.remove(j);
+        while Node::size(&u) < self.b - 1 && !Rc::ptr_eq(&Node::next(&u), &self.tail) {
+            Node::shift_from_next(&u);
+            u = Node::next(&u);
+        }
+        if Node::size(&u) == 0 {
+            Node::unlink(&u);
+        }
+        self.n -= 1;
+        x
+    }
+}
+
+/// An `ArrayDeque` that never resizes: a ring buffer of `b + 1` slots.
+#[derive(Debug)]
+struct BDeque<T> {
+    array: Box<[Option<T>]>,
+    n: usize,
+    j: usize,
+}
+
+impl<T: Clone> BDeque<T> {
+    fn new(b: usize) -> Self {
+        Self {
+            array: vec![None; b + 1].into_boxed_slice(),
+            n: 0,
+            j: 0,
+        }
+    }
+    fn size(&self) -> usize {
+        self.n
+    }
+    fn index(&self, i: usize) -> usize {
+        (self.j + i) % self.array.len()
+    }
+    fn get(&self, i: usize) -> Option<T> {
+        if i >= self.n {
+            return None;
+        }
+        self.array[self.index(i)].clone()
+    }
+    #[allow(unused)]
+    fn set(&mut self, i: usize, x: T) {
+        if i >= self.n {
+            return;
+        }
+        self.array[self.index(i)] = Some(x)
+    }
+    fn add(&mut self, i: usize, x: T) {
+        if self.n == self.array.len() {
+            panic!("block of capacity {} is full", self.array.len())
+        }
+        if i < self.n / 2 {
+            self.j = self.index(self.array.len() - 1);
+            for k in 0..i {
+                self.array.swap(self.index(k), self.index(k + 1))
+            }
+        } else {
+            for k in ((i + 1)..=self.n).rev() {
+                self.array.swap(self.index(k), self.index(k - 1))
+            }
+        }
+        self.array[self.index(i)] = Some(x);
+        self.n += 1;
+    }
+    fn remove(&mut self, i: usize) -> Option<T> {
+        if i >= self.n {
+            return None;
+        }
+        let x = self.array[self.index(i)].take();
+        if i < self.n / 2 {
+            for k in (1..=i).rev() {
+                self.array.swap(self.index(k), self.index(k - 1))
+            }
+            self.j = self.index(1);
+        } else {
+            for k in i..(self.n - 1) {
+                self.array.swap(self.index(k), self.index(k + 1))
+            }
+        }
+        self.n -= 1;
+        x
+    }
+}
+
+#[derive(Debug)]
+struct Node<T: Clone + Default + Debug> {
+    d: BDeque<T>,
+    prev: Option<WeakLink<Node<T>>>,
+    next: Option<StrongLink<Node<T>>>,
+}
+
+impl<T: Clone + Default + Debug> Node<T> {
+    fn new(b: usize) -> Self {
+        Node {
+            d: BDeque::new(b),
+            prev: None,
+            next: None,
+        }
+    }
+    fn size(u: &StrongLink<Node<T>>) -> usize {
+        u.borrow().d.size()
+    }
+    fn next(u: &StrongLink<Node<T>>) -> StrongLink<Node<T>> {
+        u.borrow().next.clone().unwrap()
+    }
+    fn prev(u: &StrongLink<Node<T>>) -> StrongLink<Node<T>> {
+        u.borrow()
+            .prev
+            .as_ref()
+            .and_then(|prev| prev.upgrade())
+            .unwrap()
+    }
+    fn add_before(w: &StrongLink<Node<T>>, b: usize) -> StrongLink<Node<T>> {
+        let prev = Node::prev(w);
+        let u = Rc::new(RefCell::new(Node::new(b)));
+        u.borrow_mut().prev = Some(Rc::downgrade(&prev));
+        u.borrow_mut().next = Some(w.clone());
+        prev.borrow_mut().next = Some(u.clone());
+        w.borrow_mut().prev = Some(Rc::downgrade(&u));
+        u
+    }
+    fn unlink(w: &StrongLink<Node<T>>) {
+        let prev = Node::prev(w);
+        let next = Node::next(w);
+        next.borrow_mut().prev = Some(Rc::downgrade(&prev));
+        prev.borrow_mut().next = Some(next);
+        w.borrow_mut().next = None;
+    }
+    /// Moves the last element of the previous block to the front of `u`.
+    fn shift_from_prev(u: &StrongLink<Node<T>>) {
+        let prev = Node::prev(u);
+        let last = Node::size(&prev) - 1;
+        let x = prev.borrow_mut().d.remove(last).unwrap();
+        u.borrow_mut().d.add(0, x);
+    }
+    /// Moves the first element of the next block to the back of `u`.
+    fn shift_from_next(u: &StrongLink<Node<T>>) {
+        let next = Node::next(u);
+        let x = next.borrow_mut().d.remove(0).unwrap();
+        let size = Node::size(u);
+        u.borrow_mut().d.add(size, x);
+    }
+}
+
+#[cfg(test)]
+mod se_list_test {
+    use super::*;
+    use crate::interfaces::list::check_against_vec;
+    #[test]
+    fn add_test() {
+        let mut list = SEList::new(2);
+        list.add(0, "world");
+        list.add(0, "hello");
+        list.add(2, "!!");
+        list.add(1, "good");
+        assert_eq!(list.size(), 4);
+        assert_eq!(list.get(0), Some("hello"));
+        assert_eq!(list.get(1), Some("good"));
+        assert_eq!(list.get(2), Some("world"));
+        assert_eq!(list.get(3), Some("!!"));
+        assert_eq!(list.get(4), None);
+    }
+    #[test]
+    fn remove_test() {
+        let mut list = SEList::new(2);
+        for i in 0..10 {
+            list.add(i, i);
+        }
+        assert_eq!(list.remove(3), Some(3));
+        assert_eq!(list.remove(10), None);
+        assert_eq!(list.remove(0), Some(0));
+        assert_eq!(list.remove(7), Some(9));
+        assert_eq!(list.size(), 7);
+        assert_eq!(list.get(2), Some(4));
+    }
+    #[test]
+    fn spread_and_gather_test() {
+        let mut list = SEList::new(3);
+        for i in 0..12 {
+            list.add(i, i);
+        }
+        assert_eq!(list.block_sizes(), vec![4, 4, 4]);
+        list.add(0, 100);
+        assert_eq!(list.block_sizes(), vec![4, 3, 3, 3]);
+        list.remove(0);
+        list.remove(0);
+        list.remove(2);
+        list.remove(4);
+        list.remove(6);
+        assert_eq!(list.block_sizes(), vec![2, 2, 2, 2]);
+        list.remove(0);
+        assert_eq!(list.block_sizes(), vec![2, 3, 2]);
+        assert_eq!(
+            (0..list.size())
+                .filter_map(|i| list.get(i))
+                .collect::<Vec<_>>(),
+            vec![2, 4, 5, 7, 8, 10, 11]
+        );
+    }
+    #[test]
+    fn bdeque_test() {
+        let mut d = BDeque::new(3);
+        for (i, x) in [(0, 1), (1, 3), (1, 2), (0, 0)] {
+            d.add(i, x);
+        }
+        assert_eq!(
+            (0..4).filter_map(|i| d.get(i)).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(d.remove(0), Some(0));
+        d.add(3, 4);
+        d.set(0, 5);
+        assert_eq!(
+            (0..4).filter_map(|i| d.get(i)).collect::<Vec<_>>(),
+            vec![5, 2, 3, 4]
+        );
+        assert_eq!(d.array.len(), 4);
+        assert_eq!(d.remove(4), None);
+    }
+    #[test]
+    #[should_panic]
+    fn full_bdeque_test() {
+        let mut d = BDeque::new(1);
+        d.add(0, 1);
+        d.add(0, 2);
+        d.add(0, 3);
+    }
+    #[test]
+    fn random_test() {
+        for b in 1..6 {
+            check_against_vec::<i32, _>(&mut SEList::new(b), 1000, |list| {
+                assert!(list.block_capacities().iter().all(|&c| c == b + 1));
+                let sizes = list.block_sizes();
+                if let Some((_, blocks)) = sizes.split_last() {
+                    assert!(blocks.iter().all(|&size| size + 1 >= b && size <= b + 1));
+                }
+            });
+        }
+    }
+}
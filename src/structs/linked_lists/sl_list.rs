@@ -1,4 +1,4 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
 
 use crate::{
     interfaces::{queue::Queue, stack::Stack},
@@ -32,6 +32,67 @@ impl<T: Clone + Debug + Eq + PartialEq> SLList<T> {
     pub fn size(&self) -> usize {
         self.n
     }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.head.clone(),
+            _list: PhantomData,
+        }
+    }
+}
+
+pub struct Iter<'a, T: Clone + Debug + Eq + PartialEq> {
+    node: Option<StrongLink<Node<T>>>,
+    _list: PhantomData<&'a SLList<T>>,
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node.take()?;
+        self.node = node.borrow().next.clone();
+        let x = node.borrow().x.clone();
+        Some(x)
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug + Eq + PartialEq>(SLList<T>);
+impl<T: Clone + Debug + Eq + PartialEq> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.n, Some(self.0.n))
+    }
+}
+
+impl<T: Clone + Debug + Eq + PartialEq> IntoIterator for SLList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+impl<'a, T: Clone + Debug + Eq + PartialEq> IntoIterator for &'a SLList<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq> FromIterator<T> for SLList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SLList::new();
+        list.extend(iter);
+        list
+    }
+}
+impl<T: Clone + Debug + Eq + PartialEq> Extend<T> for SLList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            Queue::add(self, x);
+        }
+    }
 }
 impl<T: Clone + Debug + Eq + PartialEq> Queue<T> for SLList<T> {
     fn add(&mut self, x: T) {
@@ -144,4 +205,13 @@ mod sl_list_tests {
             }
         );
     }
+    #[test]
+    fn iter_test() {
+        let mut list = vec![1, 2].into_iter().collect::<SLList<_>>();
+        list.extend(vec![3, 4]);
+        list.push(0);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!((&list).into_iter().count(), 5);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+    }
 }
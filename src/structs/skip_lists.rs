@@ -1,2 +1,5 @@
+pub mod concurrent_skip_list_sset;
+pub mod helper;
+pub mod indexed_skip_list;
 pub mod skip_list_list;
 pub mod skip_list_sset;
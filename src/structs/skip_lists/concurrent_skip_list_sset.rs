@@ -0,0 +1,464 @@
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, RwLock,
+    },
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::interfaces::{random_generator::RandomGenerator, sset::SSet};
+
+use super::helper::{self, DEFAULT_P};
+
+const MAX_HEIGHT: usize = 24;
+
+#[derive(Debug)]
+struct Node<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> {
+    x: T,
+    height: usize,
+    nexts: Vec<RwLock<Option<Arc<Node<T>>>>>,
+    lock: Mutex<()>,
+    /// Set, under `lock`, once the node is logically removed.
+    marked: AtomicBool,
+    /// Set once the node is linked in at every level.
+    fully_linked: AtomicBool,
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> Node<T> {
+    fn new(x: T, height: usize) -> Self {
+        Self {
+            x,
+            height,
+            nexts: (0..=height).map(|_| RwLock::new(None)).collect(),
+            lock: Mutex::new(()),
+            marked: AtomicBool::new(false),
+            fully_linked: AtomicBool::new(false),
+        }
+    }
+    fn get_next(&self, h: usize) -> Option<Arc<Node<T>>> {
+        self.nexts[h].read().unwrap().clone()
+    }
+    fn set_next(&self, h: usize, next: Option<Arc<Node<T>>>) {
+        *self.nexts[h].write().unwrap() = next;
+    }
+    fn is_marked(&self) -> bool {
+        self.marked.load(Ordering::Acquire)
+    }
+    fn is_fully_linked(&self) -> bool {
+        self.fully_linked.load(Ordering::Acquire)
+    }
+}
+
+fn same<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord>(
+    a: &Option<Arc<Node<T>>>,
+    b: &Option<Arc<Node<T>>>,
+) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// A sorted set that many threads can read and modify at once.
+///
+/// This is the lazy skip list of Herlihy et al.: searches take no locks,
+/// while `add` and `remove` lock only the predecessors they relink and
+/// validate them before writing. A node is removed logically by setting
+/// `marked` and then unlinked; it only counts as present once
+/// `fully_linked` is set.
+///
+/// Heights are drawn from one `rand_gen` behind a mutex, held only while a
+/// height is drawn. With one thread a seed fixes the shape; with several,
+/// the shape also depends on the order in which threads reach it.
+#[derive(Debug)]
+pub struct ConcurrentSkipListSSet<
+    T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord,
+    R: RandomGenerator = StdRng,
+> {
+    sentinel: Arc<Node<T>>,
+    n: AtomicUsize,
+    rand_gen: Mutex<R>,
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> ConcurrentSkipListSSet<T> {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    /// Builds a set whose heights are drawn from `seed`.
+    #[allow(unused)]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator>
+    ConcurrentSkipListSSet<T, R>
+{
+    #[allow(unused)]
+    pub fn with_rng(rand_gen: R) -> Self {
+        let sentinel = Arc::new(Node::new(Default::default(), MAX_HEIGHT - 1));
+        sentinel.fully_linked.store(true, Ordering::Release);
+        Self {
+            sentinel,
+            n: AtomicUsize::new(0),
+            rand_gen: Mutex::new(rand_gen),
+        }
+    }
+    #[allow(unused)]
+    pub fn size(&self) -> usize {
+        self.n.load(Ordering::Acquire)
+    }
+    #[allow(unused)]
+    pub fn find(&self, x: T) -> bool {
+        let (found, _, succs) = self.find_nodes(&x);
+        match found {
+            Some(h) => {
+                let node = succs[h].as_ref().unwrap();
+                node.is_fully_linked() && !node.is_marked()
+            }
+            None => false,
+        }
+    }
+    /// Returns the smallest element strictly greater than `x`.
+    #[allow(unused)]
+    pub fn successor(&self, x: T) -> Option<T> {
+        let (_, preds, _) = self.find_nodes(&x);
+        let mut next = preds[0].get_next(0);
+        while let Some(node) = next {
+            if node.x > x && node.is_fully_linked() && !node.is_marked() {
+                return Some(node.x.clone());
+            }
+            next = node.get_next(0);
+        }
+        None
+    }
+    #[allow(unused)]
+    pub fn add(&self, x: T) -> bool {
+        let height = self.gen_height();
+        loop {
+            let (found, preds, succs) = self.find_nodes(&x);
+            if let Some(h) = found {
+                let node = succs[h].as_ref().unwrap();
+                if !node.is_marked() {
+                    while !node.is_fully_linked() {
+                        std::hint::spin_loop();
+                    }
+                    return false;
+                }
+                continue;
+            }
+            let Some(_guards) = Self::lock_preds(&preds, height, |h, pred| {
+                let succ = &succs[h];
+                !succ.as_ref().is_some_and(|succ| succ.is_marked()) && same(&pred.get_next(h), succ)
+            }) else {
+                continue;
+            };
+            let node = Arc::new(Node::new(x, height));
+            for (h, succ) in succs.iter().enumerate().take(height + 1) {
+                node.set_next(h, succ.clone());
+            }
+            for (h, pred) in preds.iter().enumerate().take(height + 1) {
+                pred.set_next(h, Some(node.clone()));
+            }
+            node.fully_linked.store(true, Ordering::Release);
+            self.n.fetch_add(1, Ordering::AcqRel);
+            return true;
+        }
+    }
+    #[allow(unused)]
+    pub fn remove(&self, x: T) -> Option<T> {
+        let mut victim: Option<Arc<Node<T>>> = None;
+        loop {
+            let (found, preds, succs) = self.find_nodes(&x);
+            let node = match (&victim, found) {
+                (Some(victim), _) => victim.clone(),
+                (None, Some(h)) => {
+                    let node = succs[h].clone().unwrap();
+                    if !node.is_fully_linked() || node.height != h || node.is_marked() {
+                        return None;
+                    }
+                    {
+                        let _guard = node.lock.lock().unwrap();
+                        if node.is_marked() {
+                            return None;
+                        }
+                        // Once marked no add links after this node and no
+                        // remove relinks it, so its nexts are frozen.
+                        node.marked.store(true, Ordering::Release);
+                    }
+                    victim = Some(node.clone());
+                    node
+                }
+                (None, None) => return None,
+            };
+            let Some(_guards) = Self::lock_preds(&preds, node.height, |h, pred| {
+                pred.get_next(h)
+                    .is_some_and(|next| Arc::ptr_eq(&next, &node))
+            }) else {
+                continue;
+            };
+            for h in (0..=node.height).rev() {
+                preds[h].set_next(h, node.get_next(h));
+            }
+            self.n.fetch_sub(1, Ordering::AcqRel);
+            return Some(node.x.clone());
+        }
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<T> {
+        Iter {
+            node: self.sentinel.get_next(0),
+        }
+    }
+    /// Returns the level at which `x` was found, plus its predecessors and
+    /// successors at every level.
+    #[allow(clippy::type_complexity)]
+    fn find_nodes(&self, x: &T) -> (Option<usize>, Vec<Arc<Node<T>>>, Vec<Option<Arc<Node<T>>>>) {
+        let mut found = None;
+        let mut preds = vec![self.sentinel.clone(); MAX_HEIGHT];
+        let mut succs = vec![None; MAX_HEIGHT];
+        let mut pred = self.sentinel.clone();
+        for h in (0..MAX_HEIGHT).rev() {
+            let mut curr = pred.get_next(h);
+            while let Some(node) = curr.as_ref().filter(|node| &node.x < x) {
+                pred = node.clone();
+                curr = pred.get_next(h);
+            }
+            if found.is_none() && curr.as_ref().is_some_and(|node| &node.x == x) {
+                found = Some(h);
+            }
+            preds[h] = pred.clone();
+            succs[h] = curr;
+        }
+        (found, preds, succs)
+    }
+    /// Locks the distinct predecessors at levels `0..=height`, checking
+    /// each with `valid` once it is held. Returns `None`, with every lock
+    /// released, if a check fails.
+    fn lock_preds<'a>(
+        preds: &'a [Arc<Node<T>>],
+        height: usize,
+        valid: impl Fn(usize, &Node<T>) -> bool,
+    ) -> Option<Vec<MutexGuard<'a, ()>>> {
+        let mut guards = Vec::new();
+        let mut last: Option<&Arc<Node<T>>> = None;
+        for (h, pred) in preds.iter().enumerate().take(height + 1) {
+            if !last.is_some_and(|last| Arc::ptr_eq(last, pred)) {
+                guards.push(pred.lock.lock().unwrap());
+                last = Some(pred);
+            }
+            if pred.is_marked() || !valid(h, pred) {
+                return None;
+            }
+        }
+        Some(guards)
+    }
+    fn gen_height(&self) -> usize {
+        let mut rand_gen = self.rand_gen.lock().unwrap();
+        helper::gen_height(&mut *rand_gen, DEFAULT_P, MAX_HEIGHT)
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> Drop
+    for ConcurrentSkipListSSet<T, R>
+{
+    // Unlink iteratively so a long list doesn't drop recursively.
+    fn drop(&mut self) {
+        let mut next = self.sentinel.nexts[0].write().unwrap().take();
+        for h in 1..MAX_HEIGHT {
+            self.sentinel.set_next(h, None);
+        }
+        while let Some(node) = next {
+            for h in 1..=node.height {
+                node.set_next(h, None);
+            }
+            next = node.nexts[0].write().unwrap().take();
+        }
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> SSet<T>
+    for ConcurrentSkipListSSet<T, R>
+{
+    fn size(&self) -> usize {
+        ConcurrentSkipListSSet::<T, R>::size(self)
+    }
+    fn add(&mut self, x: T) -> bool {
+        ConcurrentSkipListSSet::<T, R>::add(self, x)
+    }
+    fn remove(&mut self, x: T) -> Option<T> {
+        ConcurrentSkipListSSet::<T, R>::remove(self, x)
+    }
+    fn find(&self, x: T) -> bool {
+        ConcurrentSkipListSSet::<T, R>::find(self, x)
+    }
+}
+
+/// Walks the bottom level, skipping removed nodes. Elements added or
+/// removed while iterating may or may not be seen.
+pub struct Iter<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> {
+    node: Option<Arc<Node<T>>>,
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> Iterator for Iter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.node.take()?;
+            self.node = node.get_next(0);
+            if node.is_fully_linked() && !node.is_marked() {
+                return Some(node.x.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod concurrent_skip_list_sset_test {
+    use super::*;
+    use rand::Rng;
+    use std::{collections::BTreeSet, thread};
+    #[test]
+    fn add_remove_test() {
+        let mut set = ConcurrentSkipListSSet::new();
+        assert!(SSet::add(&mut set, 3));
+        assert!(SSet::add(&mut set, 1));
+        assert!(SSet::add(&mut set, 2));
+        assert!(!SSet::add(&mut set, 1));
+        assert_eq!(SSet::size(&set), 3);
+        assert!(SSet::find(&set, 2));
+        assert_eq!(SSet::remove(&mut set, 2), Some(2));
+        assert_eq!(SSet::remove(&mut set, 2), None);
+        assert!(!SSet::find(&set, 2));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1, 3]);
+    }
+    #[test]
+    fn seed_test() {
+        fn heights<R: RandomGenerator>(set: &ConcurrentSkipListSSet<i32, R>) -> Vec<usize> {
+            let mut heights = vec![];
+            let mut next = set.sentinel.get_next(0);
+            while let Some(node) = next {
+                heights.push(node.height);
+                next = node.get_next(0);
+            }
+            heights
+        }
+        let set = ConcurrentSkipListSSet::with_seed(7);
+        let other = ConcurrentSkipListSSet::with_seed(7);
+        for x in 0..200 {
+            set.add(x);
+            other.add(x);
+        }
+        assert_eq!(heights(&set), heights(&other));
+        assert!(heights(&set).iter().any(|&h| h > 0));
+    }
+    #[test]
+    fn successor_test() {
+        let set = ConcurrentSkipListSSet::new();
+        for x in [10, 20, 30] {
+            set.add(x);
+        }
+        assert_eq!(set.successor(0), Some(10));
+        assert_eq!(set.successor(10), Some(20));
+        assert_eq!(set.successor(25), Some(30));
+        assert_eq!(set.successor(30), None);
+        set.remove(20);
+        assert_eq!(set.successor(10), Some(30));
+    }
+    #[derive(Debug, PartialEq)]
+    enum Op {
+        Add(i64, bool),
+        Remove(i64, Option<i64>),
+        Find(i64, bool),
+    }
+    #[test]
+    fn stress_test() {
+        const THREADS: i64 = 8;
+        let set = ConcurrentSkipListSSet::new();
+        // Threads share the list but each owns a residue class of keys,
+        // so each thread's log can be replayed on its own.
+        let logs = thread::scope(|s| {
+            let handles = (0..THREADS)
+                .map(|t| {
+                    let set = &set;
+                    s.spawn(move || {
+                        let mut rng = StdRng::seed_from_u64(t as u64);
+                        let mut log = Vec::new();
+                        for _ in 0..3000 {
+                            let x = rng.gen_range(0..64) * THREADS + t;
+                            log.push(match rng.gen_range(0..3) {
+                                0 => Op::Add(x, set.add(x)),
+                                1 => Op::Remove(x, set.remove(x)),
+                                _ => Op::Find(x, set.find(x)),
+                            });
+                        }
+                        log
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        let mut tobe = BTreeSet::new();
+        for log in logs {
+            for op in log {
+                let replayed = match op {
+                    Op::Add(x, _) => Op::Add(x, tobe.insert(x)),
+                    Op::Remove(x, _) => Op::Remove(x, tobe.take(&x)),
+                    Op::Find(x, _) => Op::Find(x, tobe.contains(&x)),
+                };
+                assert_eq!(op, replayed);
+            }
+        }
+        assert_eq!(set.size(), tobe.len());
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            tobe.into_iter().collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn contention_test() {
+        const THREADS: u64 = 8;
+        const KEYS: i64 = 16;
+        let set = ConcurrentSkipListSSet::new();
+        // Every thread fights over the same few keys; per key, successful
+        // adds and removes must still alternate starting with an add.
+        let counts = thread::scope(|s| {
+            let handles = (0..THREADS)
+                .map(|t| {
+                    let set = &set;
+                    s.spawn(move || {
+                        let mut rng = StdRng::seed_from_u64(t);
+                        let mut counts = vec![0i64; KEYS as usize];
+                        for _ in 0..5000 {
+                            let x = rng.gen_range(0..KEYS);
+                            if rng.gen::<bool>() {
+                                if set.add(x) {
+                                    counts[x as usize] += 1;
+                                }
+                            } else if set.remove(x).is_some() {
+                                counts[x as usize] -= 1;
+                            }
+                        }
+                        counts
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        for x in 0..KEYS {
+            let net = counts.iter().map(|counts| counts[x as usize]).sum::<i64>();
+            assert_eq!(net, set.find(x) as i64);
+        }
+        let elements = set.iter().collect::<Vec<_>>();
+        assert!(elements.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(set.size(), elements.len());
+    }
+}
@@ -0,0 +1,35 @@
+use crate::interfaces::random_generator::RandomGenerator;
+
+pub(super) const DEFAULT_P: f64 = 0.5;
+pub(super) const DEFAULT_MAX_HEIGHT: usize = 32;
+
+/// Shape of a skip list, for tuning `p` and `max_height`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkipListStats {
+    /// `level_histogram[h]` is the number of nodes of height `h`, i.e. with
+    /// `h + 1` levels.
+    pub level_histogram: Vec<usize>,
+    /// Mean number of edges, across and down, a search follows to reach an
+    /// element, over all elements.
+    pub average_search_path: f64,
+}
+
+pub(super) fn check_params(p: f64, max_height: usize) {
+    if !(0.0..=1.0).contains(&p) {
+        panic!("p must be in [0, 1] : {}", p)
+    }
+    if max_height == 0 {
+        panic!("max_height must be positive")
+    }
+}
+
+/// Draws a height: each extra level is kept with probability `p`, and no
+/// node gets more than `max_height` levels.
+pub(super) fn gen_height<R: RandomGenerator>(rand_gen: &mut R, p: f64, max_height: usize) -> usize {
+    let threshold = p * usize::MAX as f64;
+    let mut height = 0;
+    while height + 1 < max_height && (rand_gen.gen_rand() as f64) < threshold {
+        height += 1;
+    }
+    height
+}
@@ -0,0 +1,502 @@
+use std::{cell::RefCell, fmt::Debug, marker::PhantomData, ops::Range, rc::Rc};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    interfaces::{random_generator::RandomGenerator, sset::SSet},
+    types::link::StrongLink,
+};
+
+use super::helper::{self, DEFAULT_MAX_HEIGHT, DEFAULT_P};
+
+#[derive(Debug)]
+struct Node<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> {
+    x: T,
+    nexts: Vec<Option<StrongLink<Node<T>>>>,
+    /// `lengths[h]` is how many elements the edge `nexts[h]` skips over,
+    /// counting the node it lands on. Unused while `nexts[h]` is `None`.
+    lengths: Vec<usize>,
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> Node<T> {
+    fn new(x: T, height: usize) -> Self {
+        Self {
+            x,
+            nexts: vec![None; height + 1],
+            lengths: vec![0; height + 1],
+        }
+    }
+}
+
+/// An ordered skip list that also keeps the length of every edge, so
+/// elements can be reached by key and by rank alike.
+///
+/// Positions count from the sentinel: the sentinel is at 0 and the element
+/// of rank `i` is at `i + 1`.
+#[derive(Debug)]
+pub struct IndexedSkipList<
+    T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord,
+    R: RandomGenerator = StdRng,
+> {
+    sentinel: StrongLink<Node<T>>,
+    n: usize,
+    rand_gen: R,
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> IndexedSkipList<T> {
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    #[allow(unused)]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator>
+    IndexedSkipList<T, R>
+{
+    #[allow(unused)]
+    pub fn with_rng(rand_gen: R) -> Self {
+        Self {
+            sentinel: Rc::new(RefCell::new(Node::new(Default::default(), 0))),
+            n: 0,
+            rand_gen,
+        }
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.sentinel.borrow().nexts[0].clone(),
+            _list: PhantomData,
+        }
+    }
+    /// Returns how many elements are smaller than `x`.
+    #[allow(unused)]
+    pub fn rank(&self, x: &T) -> usize {
+        let (_, positions) = self.find_preds_by_key(x);
+        positions[0]
+    }
+    /// Returns the element of rank `k`, counting from 0.
+    #[allow(unused)]
+    pub fn select(&self, k: usize) -> Option<T> {
+        if k >= self.n {
+            return None;
+        }
+        let (preds, _) = self.find_preds_by_index(k);
+        let next = preds[0].borrow().nexts[0].clone();
+        next.map(|node| node.borrow().x.clone())
+    }
+    /// Removes every element in `range` and returns them in order.
+    #[allow(unused)]
+    pub fn remove_range(&mut self, range: Range<T>) -> Vec<T> {
+        if range.start >= range.end {
+            return Vec::new();
+        }
+        let (preds, pred_positions) = self.find_preds_by_key(&range.start);
+        let (lasts, last_positions) = self.find_preds_by_key(&range.end);
+        let m = last_positions[0] - pred_positions[0];
+        if m == 0 {
+            return Vec::new();
+        }
+        let first = preds[0].borrow().nexts[0].clone();
+        for h in 0..preds.len() {
+            let (succ, length) = {
+                let last = lasts[h].borrow();
+                (last.nexts[h].clone(), last_positions[h] + last.lengths[h])
+            };
+            let mut pred = preds[h].borrow_mut();
+            pred.lengths[h] = if succ.is_some() {
+                length - m - pred_positions[h]
+            } else {
+                0
+            };
+            pred.nexts[h] = succ;
+        }
+        let mut removed = Vec::with_capacity(m);
+        let mut node = first;
+        for _ in 0..m {
+            let current = node.unwrap();
+            removed.push(current.borrow().x.clone());
+            node = Self::unlink(&current);
+        }
+        self.n -= m;
+        self.trim();
+        removed
+    }
+    fn find_preds_by_key(&self, x: &T) -> (Vec<StrongLink<Node<T>>>, Vec<usize>) {
+        self.find_preds(|node, _| &node.x < x)
+    }
+    /// Finds, at each level, the last node at position `k` or before.
+    fn find_preds_by_index(&self, k: usize) -> (Vec<StrongLink<Node<T>>>, Vec<usize>) {
+        self.find_preds(|_, position| position <= k)
+    }
+    /// Walks down from the top level, following each edge while `go_right`
+    /// holds for the node (and position) it lands on. Returns the node the
+    /// walk stopped at on every level, with its position.
+    fn find_preds(
+        &self,
+        go_right: impl Fn(&Node<T>, usize) -> bool,
+    ) -> (Vec<StrongLink<Node<T>>>, Vec<usize>) {
+        let height = self.height();
+        let mut preds = vec![self.sentinel.clone(); height + 1];
+        let mut positions = vec![0; height + 1];
+        let mut u = self.sentinel.clone();
+        let mut position = 0;
+        for h in (0..=height).rev() {
+            loop {
+                let (next, length) = {
+                    let node = u.borrow();
+                    (node.nexts[h].clone(), node.lengths[h])
+                };
+                match next {
+                    Some(next) if go_right(&next.borrow(), position + length) => {
+                        position += length;
+                        u = next;
+                    }
+                    _ => break,
+                }
+            }
+            preds[h] = u.clone();
+            positions[h] = position;
+        }
+        (preds, positions)
+    }
+    /// Detaches `node` from everything it points to and returns its level-0
+    /// successor, so a run of nodes can be freed without deep recursion.
+    fn unlink(node: &StrongLink<Node<T>>) -> Option<StrongLink<Node<T>>> {
+        let mut node = node.borrow_mut();
+        let next = node.nexts[0].take();
+        node.nexts.iter_mut().for_each(|next| *next = None);
+        next
+    }
+    fn grow(&self, height: usize) {
+        let mut sentinel = self.sentinel.borrow_mut();
+        while sentinel.nexts.len() <= height {
+            sentinel.nexts.push(None);
+            sentinel.lengths.push(0);
+        }
+    }
+    fn trim(&mut self) {
+        let mut sentinel = self.sentinel.borrow_mut();
+        while sentinel.nexts.len() > 1 && sentinel.nexts.last().unwrap().is_none() {
+            sentinel.nexts.pop();
+            sentinel.lengths.pop();
+        }
+    }
+    fn gen_height(&mut self) -> usize {
+        helper::gen_height(&mut self.rand_gen, DEFAULT_P, DEFAULT_MAX_HEIGHT)
+    }
+    fn height(&self) -> usize {
+        self.sentinel.borrow().nexts.len() - 1
+    }
+    #[cfg(test)]
+    fn check_lengths(&self) {
+        let mut node = Some(self.sentinel.clone());
+        let mut position = 0;
+        let mut positions = Vec::new();
+        while let Some(current) = node {
+            positions.push((current.clone(), position));
+            position += 1;
+            node = current.borrow().nexts[0].clone();
+        }
+        assert_eq!(positions.len(), self.n + 1);
+        for (node, position) in positions.iter() {
+            let node = node.borrow();
+            for (next, length) in node.nexts.iter().zip(node.lengths.iter()) {
+                if let Some(next) = next {
+                    let (_, next_position) = positions
+                        .iter()
+                        .find(|(candidate, _)| Rc::ptr_eq(candidate, next))
+                        .unwrap();
+                    assert_eq!(next_position - position, *length);
+                }
+            }
+        }
+    }
+}
+
+impl<
+        T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord,
+        R: RandomGenerator + Clone,
+    > IndexedSkipList<T, R>
+{
+    /// Keeps the first `k` elements and returns a list of the rest.
+    #[allow(unused)]
+    pub fn split_at(&mut self, k: usize) -> Self {
+        let k = k.min(self.n);
+        let (preds, positions) = self.find_preds_by_index(k);
+        let mut other = Self::with_rng(self.rand_gen.clone());
+        other.grow(preds.len() - 1);
+        {
+            let mut sentinel = other.sentinel.borrow_mut();
+            for h in 0..preds.len() {
+                let mut pred = preds[h].borrow_mut();
+                sentinel.nexts[h] = pred.nexts[h].take();
+                sentinel.lengths[h] = if sentinel.nexts[h].is_some() {
+                    positions[h] + pred.lengths[h] - k
+                } else {
+                    0
+                };
+                pred.lengths[h] = 0;
+            }
+        }
+        other.n = self.n - k;
+        self.n = k;
+        self.trim();
+        other.trim();
+        other
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> Drop
+    for IndexedSkipList<T, R>
+{
+    fn drop(&mut self) {
+        let mut node = Self::unlink(&self.sentinel);
+        while let Some(current) = node {
+            node = Self::unlink(&current);
+        }
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> SSet<T>
+    for IndexedSkipList<T, R>
+{
+    fn size(&self) -> usize {
+        self.n
+    }
+    fn add(&mut self, x: T) -> bool {
+        let (mut preds, mut positions) = self.find_preds_by_key(&x);
+        let exists = preds[0].borrow().nexts[0]
+            .as_ref()
+            .is_some_and(|next| next.borrow().x == x);
+        if exists {
+            return false;
+        }
+        let height = self.gen_height();
+        self.grow(height);
+        while preds.len() <= height {
+            preds.push(self.sentinel.clone());
+            positions.push(0);
+        }
+        let i = positions[0] + 1;
+        let node = Rc::new(RefCell::new(Node::new(x, height)));
+        for h in 0..preds.len() {
+            let mut pred = preds[h].borrow_mut();
+            if h <= height {
+                let mut new = node.borrow_mut();
+                new.nexts[h] = pred.nexts[h].take();
+                new.lengths[h] = if new.nexts[h].is_some() {
+                    positions[h] + pred.lengths[h] + 1 - i
+                } else {
+                    0
+                };
+                pred.nexts[h] = Some(node.clone());
+                pred.lengths[h] = i - positions[h];
+            } else if pred.nexts[h].is_some() {
+                pred.lengths[h] += 1;
+            }
+        }
+        self.n += 1;
+        true
+    }
+    fn remove(&mut self, x: T) -> Option<T> {
+        let (preds, _) = self.find_preds_by_key(&x);
+        let target = preds[0].borrow().nexts[0]
+            .clone()
+            .filter(|next| next.borrow().x == x)?;
+        for (h, pred) in preds.iter().enumerate() {
+            let mut pred = pred.borrow_mut();
+            let is_target = pred.nexts[h]
+                .as_ref()
+                .is_some_and(|next| Rc::ptr_eq(next, &target));
+            if is_target {
+                let mut target = target.borrow_mut();
+                pred.nexts[h] = target.nexts[h].take();
+                pred.lengths[h] = if pred.nexts[h].is_some() {
+                    pred.lengths[h] + target.lengths[h] - 1
+                } else {
+                    0
+                };
+            } else if pred.nexts[h].is_some() {
+                pred.lengths[h] -= 1;
+            }
+        }
+        self.n -= 1;
+        self.trim();
+        let x = target.borrow().x.clone();
+        Some(x)
+    }
+    fn find(&self, x: T) -> bool {
+        let (preds, _) = self.find_preds_by_key(&x);
+        let pred = preds[0].borrow();
+        pred.nexts[0]
+            .as_ref()
+            .is_some_and(|next| next.borrow().x == x)
+    }
+}
+
+pub struct Iter<'a, T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> {
+    node: Option<StrongLink<Node<T>>>,
+    _list: PhantomData<&'a Node<T>>,
+}
+impl<'a, T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node.take()?;
+        self.node = node.borrow().nexts[0].clone();
+        let x = node.borrow().x.clone();
+        Some(x)
+    }
+}
+
+impl<'a, T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator>
+    IntoIterator for &'a IndexedSkipList<T, R>
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> FromIterator<T>
+    for IndexedSkipList<T>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = IndexedSkipList::new();
+        list.extend(iter);
+        list
+    }
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> Extend<T>
+    for IndexedSkipList<T, R>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod indexed_skip_list_test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+    #[test]
+    fn add_remove_test() {
+        let mut list = IndexedSkipList::with_seed(1);
+        assert!(list.add(3));
+        assert!(list.add(1));
+        assert!(list.add(2));
+        assert!(!list.add(2));
+        assert_eq!(list.size(), 3);
+        assert!(list.find(1));
+        assert!(!list.find(4));
+        assert_eq!(list.remove(1), Some(1));
+        assert_eq!(list.remove(1), None);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![2, 3]);
+        list.check_lengths();
+    }
+    #[test]
+    fn rank_select_test() {
+        let list = (0..50).map(|i| i * 2).collect::<IndexedSkipList<_>>();
+        assert_eq!(list.rank(&0), 0);
+        assert_eq!(list.rank(&1), 1);
+        assert_eq!(list.rank(&10), 5);
+        assert_eq!(list.rank(&1000), 50);
+        assert_eq!(list.select(0), Some(0));
+        assert_eq!(list.select(5), Some(10));
+        assert_eq!(list.select(49), Some(98));
+        assert_eq!(list.select(50), None);
+        list.check_lengths();
+    }
+    #[test]
+    fn remove_range_test() {
+        let mut list = (0..20).collect::<IndexedSkipList<_>>();
+        assert_eq!(list.remove_range(5..9), vec![5, 6, 7, 8]);
+        assert_eq!(list.remove_range(5..9), vec![]);
+        let (lo, hi) = (9, 5);
+        assert_eq!(list.remove_range(lo..hi), vec![]);
+        assert_eq!(list.size(), 16);
+        assert_eq!(list.select(5), Some(9));
+        assert_eq!(list.rank(&9), 5);
+        list.check_lengths();
+        assert_eq!(list.remove_range(-10..100).len(), 16);
+        assert_eq!(list.size(), 0);
+        assert_eq!(list.iter().next(), None);
+        list.check_lengths();
+    }
+    #[test]
+    fn split_at_test() {
+        let mut list = (0..20).collect::<IndexedSkipList<_>>();
+        let other = list.split_at(7);
+        assert_eq!(list.iter().collect::<Vec<_>>(), (0..7).collect::<Vec<_>>());
+        assert_eq!(
+            other.iter().collect::<Vec<_>>(),
+            (7..20).collect::<Vec<_>>()
+        );
+        assert_eq!(list.size(), 7);
+        assert_eq!(other.size(), 13);
+        assert_eq!(other.select(0), Some(7));
+        assert_eq!(other.rank(&10), 3);
+        list.check_lengths();
+        other.check_lengths();
+        let empty = list.split_at(100);
+        assert_eq!(empty.size(), 0);
+        assert_eq!(list.size(), 7);
+    }
+    #[test]
+    fn random_test() {
+        let mut rng = thread_rng();
+        let mut list = IndexedSkipList::new();
+        let mut tobe: Vec<i32> = Vec::new();
+        for _ in 0..2000 {
+            let x = rng.gen_range(0..300);
+            match rng.gen_range(0..5) {
+                0 | 1 => {
+                    let result = list.add(x);
+                    match tobe.binary_search(&x) {
+                        Ok(_) => assert!(!result),
+                        Err(i) => {
+                            assert!(result);
+                            tobe.insert(i, x);
+                        }
+                    }
+                }
+                2 => {
+                    let result = list.remove(x);
+                    match tobe.binary_search(&x) {
+                        Ok(i) => assert_eq!(result, Some(tobe.remove(i))),
+                        Err(_) => assert_eq!(result, None),
+                    }
+                }
+                3 => {
+                    let hi = x + rng.gen_range(0..10);
+                    let removed = list.remove_range(x..hi);
+                    let expected = tobe
+                        .iter()
+                        .copied()
+                        .filter(|y| (x..hi).contains(y))
+                        .collect::<Vec<_>>();
+                    tobe.retain(|y| !(x..hi).contains(y));
+                    assert_eq!(removed, expected);
+                }
+                _ => {
+                    let rank = tobe.partition_point(|y| *y < x);
+                    assert_eq!(list.rank(&x), rank);
+                    assert_eq!(list.select(rank), tobe.get(rank).copied());
+                }
+            }
+            assert_eq!(list.size(), tobe.len());
+        }
+        list.check_lengths();
+        assert_eq!(list.iter().collect::<Vec<_>>(), tobe);
+        let k = tobe.len() / 3;
+        let other = list.split_at(k);
+        list.check_lengths();
+        other.check_lengths();
+        assert_eq!(list.iter().collect::<Vec<_>>(), tobe[..k]);
+        assert_eq!(other.iter().collect::<Vec<_>>(), tobe[k..]);
+    }
+}
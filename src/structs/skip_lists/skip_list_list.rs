@@ -1,7 +1,9 @@
-use rand::{thread_rng, Rng};
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
 
-use crate::interfaces::list::List;
+use crate::interfaces::{list::List, random_generator::RandomGenerator};
+
+use super::helper::{self, SkipListStats, DEFAULT_MAX_HEIGHT, DEFAULT_P};
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 struct Node<T: Clone + Debug + Default + PartialEq + Eq> {
     x: T,
@@ -64,16 +66,117 @@ impl<T: Clone + Debug + Default + PartialEq + Eq> Drop for Node<T> {
         )
     }
 }
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub struct SkipListList<T: Clone + Debug + Default + PartialEq + Eq> {
+#[derive(Clone, Debug)]
+pub struct SkipListList<T: Clone + Debug + Default + PartialEq + Eq, R: RandomGenerator = StdRng> {
     sentinel: Rc<RefCell<Node<T>>>,
     n: usize,
+    rand_gen: R,
+    p: f64,
+    max_height: usize,
 }
 impl<T: Clone + Debug + Default + PartialEq + Eq> SkipListList<T> {
     #[allow(unused)]
     pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    /// Builds a list whose heights, and so whose shape, are fixed by `seed`.
+    #[allow(unused)]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+    /// Builds a list where each node climbs one more level with probability
+    /// `p`, up to `max_height` levels.
+    #[allow(unused)]
+    pub fn with_params(p: f64, max_height: usize) -> Self {
+        Self::with_rng_and_params(StdRng::from_entropy(), p, max_height)
+    }
+}
+impl<T: Clone + Debug + Default + PartialEq + Eq> Default for SkipListList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone + Debug + Default + PartialEq + Eq, R: RandomGenerator> PartialEq
+    for SkipListList<T, R>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.sentinel == other.sentinel
+    }
+}
+impl<T: Clone + Debug + Default + PartialEq + Eq, R: RandomGenerator> Eq for SkipListList<T, R> {}
+impl<T: Clone + Debug + Default + PartialEq + Eq, R: RandomGenerator> SkipListList<T, R> {
+    #[allow(unused)]
+    pub fn with_rng(rand_gen: R) -> Self {
+        Self::with_rng_and_params(rand_gen, DEFAULT_P, DEFAULT_MAX_HEIGHT)
+    }
+    #[allow(unused)]
+    pub fn with_rng_and_params(rand_gen: R, p: f64, max_height: usize) -> Self {
+        helper::check_params(p, max_height);
         let sentinel = Rc::new(RefCell::new(Node::new(Default::default(), 0)));
-        Self { sentinel, n: 0 }
+        Self {
+            sentinel,
+            n: 0,
+            rand_gen,
+            p,
+            max_height,
+        }
+    }
+    #[allow(unused)]
+    pub fn stats(&self) -> SkipListStats {
+        let mut level_histogram = Vec::new();
+        let mut total_path = 0;
+        let mut node = self.sentinel.borrow().get_next(0);
+        let mut i = 0;
+        while let Some(current) = node {
+            let height = current.borrow().height;
+            if level_histogram.len() <= height {
+                level_histogram.resize(height + 1, 0);
+            }
+            level_histogram[height] += 1;
+            total_path += self.search_path_len(i);
+            node = current.borrow().get_next(0);
+            i += 1;
+        }
+        SkipListStats {
+            level_histogram,
+            average_search_path: if self.n == 0 {
+                0.0
+            } else {
+                total_path as f64 / self.n as f64
+            },
+        }
+    }
+    /// Counts the edges, across and down, a search follows to reach index
+    /// `i`.
+    fn search_path_len(&self, i: usize) -> usize {
+        let mut prev = self.sentinel.clone();
+        let mut prev_index = -1;
+        let mut len = 0;
+        for h in (0..=self.height()).rev() {
+            loop {
+                let next = prev.borrow().get_next(h);
+                let to_next = prev_index + prev.borrow().get_length(h) as isize;
+                match next {
+                    Some(next) if to_next < i as isize => {
+                        prev = next;
+                        prev_index = to_next;
+                        len += 1;
+                    }
+                    _ => break,
+                }
+            }
+            if h > 0 {
+                len += 1;
+            }
+        }
+        len + 1
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.sentinel.borrow().get_next(0),
+            _list: PhantomData,
+        }
     }
     fn find_pred(&self, i: usize) -> Rc<RefCell<Node<T>>> {
         let mut prev_node = self.sentinel.clone();
@@ -161,21 +264,26 @@ impl<T: Clone + Debug + Default + PartialEq + Eq> SkipListList<T> {
             }
         }
     }
-    fn gen_height(&self) -> usize {
-        let mut height = 0;
-        let mut rng = thread_rng();
-        let mut random: bool = rng.gen();
-        while random {
-            height += 1;
-            random = rng.gen();
+    #[cfg(test)]
+    fn heights(&self) -> Vec<usize> {
+        let mut heights = Vec::new();
+        let mut node = self.sentinel.borrow().get_next(0);
+        while let Some(current) = node {
+            heights.push(current.borrow().height);
+            node = current.borrow().get_next(0);
         }
-        height
+        heights
+    }
+    fn gen_height(&mut self) -> usize {
+        helper::gen_height(&mut self.rand_gen, self.p, self.max_height)
     }
     fn height(&self) -> usize {
         self.sentinel.borrow().height
     }
 }
-impl<T: Clone + Debug + Default + PartialEq + Eq> List<T> for SkipListList<T> {
+impl<T: Clone + Debug + Default + PartialEq + Eq, R: RandomGenerator> List<T>
+    for SkipListList<T, R>
+{
     fn add(&mut self, i: usize, x: T) -> () {
         let h = self.gen_height();
         self.add_base(i, x, h);
@@ -262,11 +370,117 @@ impl<T: Clone + Debug + Default + PartialEq + Eq> List<T> for SkipListList<T> {
     }
 }
 
+pub struct Iter<'a, T: Clone + Debug + Default + PartialEq + Eq> {
+    node: Option<Rc<RefCell<Node<T>>>>,
+    _list: PhantomData<&'a SkipListList<T>>,
+}
+impl<'a, T: Clone + Debug + Default + PartialEq + Eq> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node.take()?;
+        self.node = node.borrow().get_next(0);
+        let x = node.borrow().x.clone();
+        Some(x)
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug + Default + PartialEq + Eq> {
+    node: Option<Rc<RefCell<Node<T>>>>,
+}
+impl<T: Clone + Debug + Default + PartialEq + Eq> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node.take()?;
+        self.node = node
+            .borrow_mut()
+            .nexts
+            .get_mut(0)
+            .and_then(|next| next.take());
+        let x = node.borrow().x.clone();
+        Some(x)
+    }
+}
+
+impl<T: Clone + Debug + Default + PartialEq + Eq, R: RandomGenerator> IntoIterator
+    for SkipListList<T, R>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        let node = self.sentinel.borrow().get_next(0);
+        IntoIter { node }
+    }
+}
+impl<'a, T: Clone + Debug + Default + PartialEq + Eq, R: RandomGenerator> IntoIterator
+    for &'a SkipListList<T, R>
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + Default + PartialEq + Eq> FromIterator<T> for SkipListList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SkipListList::new();
+        list.extend(iter);
+        list
+    }
+}
+impl<T: Clone + Debug + Default + PartialEq + Eq, R: RandomGenerator> Extend<T>
+    for SkipListList<T, R>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod skip_list_list_test {
     use super::*;
     use std::{cell::RefCell, rc::Rc};
     #[test]
+    fn params_test() {
+        let mut list = SkipListList::with_rng_and_params(StdRng::seed_from_u64(3), 0.25, 4);
+        list.extend(0..1000);
+        let stats = list.stats();
+        assert!(stats.level_histogram.len() <= 4);
+        assert_eq!(stats.level_histogram.iter().sum::<usize>(), 1000);
+        assert!(stats.level_histogram[0] > 2 * stats.level_histogram[1]);
+        let mut flat = SkipListList::with_params(0.0, 8);
+        flat.extend(0..10);
+        assert_eq!(flat.stats().level_histogram, vec![10]);
+        assert_eq!(flat.stats().average_search_path, 5.5);
+    }
+    #[test]
+    fn stats_test() {
+        let mut list = SkipListList::with_seed(5);
+        assert_eq!(list.stats().average_search_path, 0.0);
+        list.extend(0..1024);
+        let stats = list.stats();
+        assert_eq!(stats.level_histogram.iter().sum::<usize>(), 1024);
+        assert!(stats.average_search_path <= 4.0 * 10.0);
+        assert_eq!(list.get(700), Some(700));
+    }
+    #[test]
+    fn seed_test() {
+        let mut list = SkipListList::with_seed(42);
+        let mut other = SkipListList::with_seed(42);
+        for i in 0..200 {
+            list.add(i / 2, i);
+            other.add(i / 2, i);
+        }
+        assert_eq!(list.heights(), other.heights());
+        assert_eq!(list.remove(50), other.remove(50));
+        assert_eq!(list.heights(), other.heights());
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            other.iter().collect::<Vec<_>>()
+        );
+    }
+    #[test]
     fn remove_test() {
         let mut list = SkipListList::new();
         list.add(0, 0);
@@ -280,6 +494,16 @@ mod skip_list_list_test {
         assert_eq!(list.remove(2), Some(3));
     }
     #[test]
+    fn iter_test() {
+        let mut list = (0..3).collect::<SkipListList<_>>();
+        list.extend(4..6);
+        list.add(3, 3);
+        assert_eq!(list.size(), 6);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!((&list).into_iter().count(), 6);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+    }
+    #[test]
     fn set_test() {
         let mut list = SkipListList::new();
         list.add(0, 0);
@@ -345,6 +569,15 @@ mod skip_list_list_test {
 
         sentinel.borrow_mut().set_length(4, 4);
         sentinel.borrow_mut().set_next(4, Some(three_node.clone()));
-        assert_eq!(list, SkipListList { n: 5, sentinel })
+        assert_eq!(
+            list,
+            SkipListList {
+                n: 5,
+                sentinel,
+                rand_gen: StdRng::seed_from_u64(0),
+                p: DEFAULT_P,
+                max_height: DEFAULT_MAX_HEIGHT,
+            }
+        )
     }
 }
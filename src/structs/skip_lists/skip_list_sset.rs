@@ -1,8 +1,10 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
 
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, SeedableRng};
 
-use crate::interfaces::sset::SSet;
+use crate::interfaces::{random_generator::RandomGenerator, sset::SSet};
+
+use super::helper::{self, SkipListStats, DEFAULT_MAX_HEIGHT, DEFAULT_P};
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 struct Node<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> {
     x: T,
@@ -42,17 +44,105 @@ impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> Node<T> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct SkipListSSet<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> {
+#[derive(Clone, Debug)]
+pub struct SkipListSSet<
+    T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord,
+    R: RandomGenerator = StdRng,
+> {
     sentinel: Rc<RefCell<Node<T>>>,
     n: usize,
+    rand_gen: R,
+    p: f64,
+    max_height: usize,
 }
 
 impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> SkipListSSet<T> {
     #[allow(unused)]
     pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    /// Builds a list whose heights, and so whose shape, are fixed by `seed`.
+    #[allow(unused)]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+    /// Builds a list where each node climbs one more level with probability
+    /// `p`, up to `max_height` levels.
+    #[allow(unused)]
+    pub fn with_params(p: f64, max_height: usize) -> Self {
+        Self::with_rng_and_params(StdRng::from_entropy(), p, max_height)
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator>
+    SkipListSSet<T, R>
+{
+    #[allow(unused)]
+    pub fn with_rng(rand_gen: R) -> Self {
+        Self::with_rng_and_params(rand_gen, DEFAULT_P, DEFAULT_MAX_HEIGHT)
+    }
+    #[allow(unused)]
+    pub fn with_rng_and_params(rand_gen: R, p: f64, max_height: usize) -> Self {
+        helper::check_params(p, max_height);
         let sentinel = Rc::new(RefCell::new(Node::new(Default::default(), 0)));
-        Self { n: 0, sentinel }
+        Self {
+            n: 0,
+            sentinel,
+            rand_gen,
+            p,
+            max_height,
+        }
+    }
+    #[allow(unused)]
+    pub fn stats(&self) -> SkipListStats {
+        let mut level_histogram = Vec::new();
+        let mut total_path = 0;
+        let mut node = self.get_next(0);
+        while let Some(current) = node {
+            let height = current.borrow().height;
+            if level_histogram.len() <= height {
+                level_histogram.resize(height + 1, 0);
+            }
+            level_histogram[height] += 1;
+            total_path += self.search_path_len(&current.borrow().x);
+            node = current.borrow().get_next(0);
+        }
+        SkipListStats {
+            level_histogram,
+            average_search_path: if self.n == 0 {
+                0.0
+            } else {
+                total_path as f64 / self.n as f64
+            },
+        }
+    }
+    /// Counts the edges, across and down, a search follows to reach `x`.
+    fn search_path_len(&self, x: &T) -> usize {
+        let mut prev = self.sentinel.clone();
+        let mut len = 0;
+        for h in (0..=self.height()).rev() {
+            loop {
+                let next = prev.borrow().get_next(h);
+                match next {
+                    Some(next) if &next.borrow().x < x => {
+                        prev = next.clone();
+                        len += 1;
+                    }
+                    _ => break,
+                }
+            }
+            if h > 0 {
+                len += 1;
+            }
+        }
+        len + 1
+    }
+    #[allow(unused)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.sentinel.borrow().get_next(0),
+            _list: PhantomData,
+        }
     }
     pub fn find_pred_node(&self, x: T) -> Option<T> {
         if self.n == 0 {
@@ -125,15 +215,18 @@ impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> SkipListSSe
         self.n += 1;
         true
     }
-    fn gen_height(&self) -> usize {
-        let mut height = 0;
-        let mut rng = thread_rng();
-        let mut random: bool = rng.gen();
-        while random {
-            height += 1;
-            random = rng.gen();
+    #[cfg(test)]
+    fn heights(&self) -> Vec<usize> {
+        let mut heights = Vec::new();
+        let mut node = self.get_next(0);
+        while let Some(current) = node {
+            heights.push(current.borrow().height);
+            node = current.borrow().get_next(0);
         }
-        height
+        heights
+    }
+    fn gen_height(&mut self) -> usize {
+        helper::gen_height(&mut self.rand_gen, self.p, self.max_height)
     }
     fn height(&self) -> usize {
         self.sentinel.borrow().height
@@ -149,7 +242,21 @@ impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> SkipListSSe
     }
 }
 
-impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> SSet<T> for SkipListSSet<T> {
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> PartialEq
+    for SkipListSSet<T, R>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.sentinel == other.sentinel
+    }
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> Eq
+    for SkipListSSet<T, R>
+{
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> SSet<T>
+    for SkipListSSet<T, R>
+{
     fn add(&mut self, x: T) -> bool {
         let height = self.gen_height();
         self.add_base(x, height)
@@ -213,6 +320,75 @@ impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> SSet<T> for
     }
 }
 
+pub struct Iter<'a, T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> {
+    node: Option<Rc<RefCell<Node<T>>>>,
+    _list: PhantomData<&'a SkipListSSet<T>>,
+}
+impl<'a, T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node.take()?;
+        self.node = node.borrow().get_next(0);
+        let x = node.borrow().x.clone();
+        Some(x)
+    }
+}
+
+pub struct IntoIter<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> {
+    node: Option<Rc<RefCell<Node<T>>>>,
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node.take()?;
+        self.node = node
+            .borrow_mut()
+            .nexts
+            .get_mut(0)
+            .and_then(|next| next.take());
+        let x = node.borrow().x.clone();
+        Some(x)
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator>
+    IntoIterator for SkipListSSet<T, R>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        let node = self.sentinel.borrow().get_next(0);
+        IntoIter { node }
+    }
+}
+impl<'a, T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator>
+    IntoIterator for &'a SkipListSSet<T, R>
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord> FromIterator<T>
+    for SkipListSSet<T>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SkipListSSet::new();
+        list.extend(iter);
+        list
+    }
+}
+impl<T: Clone + Debug + PartialEq + Eq + Default + PartialOrd + Ord, R: RandomGenerator> Extend<T>
+    for SkipListSSet<T, R>
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
 #[cfg(test)]
 mod skip_list_sset_test {
     use super::*;
@@ -271,7 +447,13 @@ mod skip_list_sset_test {
         sentinel.set_next(9, Some(seven_node.clone()));
         sentinel.set_next(10, Some(seven_node.clone()));
         let sentinel = Rc::new(RefCell::new(sentinel));
-        let tobe = SkipListSSet { sentinel, n: 6 };
+        let tobe = SkipListSSet {
+            sentinel,
+            n: 6,
+            rand_gen: StdRng::seed_from_u64(0),
+            p: DEFAULT_P,
+            max_height: DEFAULT_MAX_HEIGHT,
+        };
         assert_eq!(list, tobe);
         assert!(!list.add_base(1, 4))
     }
@@ -315,11 +497,59 @@ mod skip_list_sset_test {
         sentinel.set_next(9, Some(seven_node.clone()));
         sentinel.set_next(10, Some(seven_node.clone()));
         let sentinel = Rc::new(RefCell::new(sentinel));
-        let tobe = SkipListSSet { sentinel, n: 4 };
+        let tobe = SkipListSSet {
+            sentinel,
+            n: 4,
+            rand_gen: StdRng::seed_from_u64(0),
+            p: DEFAULT_P,
+            max_height: DEFAULT_MAX_HEIGHT,
+        };
 
         assert_eq!(list, tobe);
     }
     #[test]
+    fn iter_test() {
+        let mut list = vec![3, 0, 2].into_iter().collect::<SkipListSSet<_>>();
+        list.extend(vec![1, 5, 2]);
+        assert_eq!(list.size(), 5);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 5]);
+        assert_eq!((&list).into_iter().count(), 5);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 5]);
+    }
+    #[test]
+    fn seed_test() {
+        let keys = (0..200).map(|i| (i * 37) % 200).collect::<Vec<_>>();
+        let mut list = SkipListSSet::with_seed(42);
+        let mut other = SkipListSSet::with_seed(42);
+        list.extend(keys.clone());
+        other.extend(keys);
+        assert_eq!(list.heights(), other.heights());
+        assert_eq!(list.remove(100), other.remove(100));
+        assert_eq!(list.heights(), other.heights());
+    }
+    #[test]
+    fn params_test() {
+        let mut list = SkipListSSet::with_rng_and_params(StdRng::seed_from_u64(3), 0.25, 4);
+        list.extend(0..1000);
+        let stats = list.stats();
+        assert!(stats.level_histogram.len() <= 4);
+        assert_eq!(stats.level_histogram.iter().sum::<usize>(), 1000);
+        assert!(stats.level_histogram[0] > 2 * stats.level_histogram[1]);
+        let mut flat = SkipListSSet::with_params(0.0, 8);
+        flat.extend(0..10);
+        assert_eq!(flat.stats().level_histogram, vec![10]);
+        assert_eq!(flat.stats().average_search_path, 5.5);
+    }
+    #[test]
+    fn stats_test() {
+        let mut list = SkipListSSet::with_seed(5);
+        assert_eq!(list.stats().average_search_path, 0.0);
+        list.extend(0..1024);
+        let stats = list.stats();
+        assert_eq!(stats.level_histogram.iter().sum::<usize>(), 1024);
+        assert!(stats.average_search_path <= 4.0 * 10.0);
+    }
+    #[test]
     fn find_test() {
         let mut list = SkipListSSet::new();
         list.add(0);